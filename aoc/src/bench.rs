@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::Day;
+
+/// Timing statistics over repeated runs of one stage.
+#[derive(Clone, Copy, Debug)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    /// Runs `f` `iterations` times (at least once) and collects the statistics.
+    pub fn measure<T>(iterations: usize, mut f: impl FnMut() -> T) -> Self {
+        let mut times = (0..iterations.max(1))
+            .map(|_| {
+                let start = Instant::now();
+                black_box(f());
+                start.elapsed()
+            })
+            .collect::<Vec<Duration>>();
+        times.sort();
+        Self {
+            min: times[0],
+            median: times[times.len() / 2],
+            max: times[times.len() - 1],
+        }
+    }
+
+    /// Input bytes processed per second, based on the median time.
+    pub fn throughput(&self, bytes: usize) -> f64 {
+        bytes as f64 / self.median.as_secs_f64().max(f64::MIN_POSITIVE)
+    }
+}
+
+/// A benchmarked stage of a day: "parse", "part1" or "part2".
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    pub day: u32,
+    pub stage: &'static str,
+    pub input_len: usize,
+    pub timing: Timing,
+}
+
+/// Benchmarks parsing and both parts of `day` on `input`. Parts are timed on a
+/// single parse of the input, so each stage is measured on its own.
+pub fn bench_day(day: &Day, input: &str, iterations: usize) -> Result<Vec<Measurement>, crate::ParseError> {
+    let parsed = day.parse(input)?;
+    let measurement = |stage, timing| Measurement { day: day.day, stage, input_len: input.len(), timing };
+    Ok(vec![
+        measurement("parse", Timing::measure(iterations, || day.parse(input))),
+        measurement("part1", Timing::measure(iterations, || day.solve(1, &parsed))),
+        measurement("part2", Timing::measure(iterations, || day.solve(2, &parsed))),
+    ])
+}
+
+/// Median times of a previous run, keyed by day and stage.
+pub type Baseline = HashMap<(u32, String), Duration>;
+
+/// Saves the median times as a baseline, one `day stage nanoseconds` line per stage.
+pub fn save_baseline(path: &Path, measurements: &[Measurement]) -> std::io::Result<()> {
+    let contents = measurements.iter()
+        .map(|m| format!("{} {} {}\n", m.day, m.stage, m.timing.median.as_nanos()))
+        .collect::<String>();
+    std::fs::write(path, contents)
+}
+
+/// Loads a baseline written by [`save_baseline`]. Malformed lines are skipped.
+pub fn load_baseline(path: &Path) -> std::io::Result<Baseline> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(crate::parse::lines(&contents)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let day = fields.next()?.parse::<u32>().ok()?;
+            let stage = fields.next()?.to_string();
+            let nanos = fields.next()?.parse::<u64>().ok()?;
+            Some(((day, stage), Duration::from_nanos(nanos)))
+        })
+        .collect())
+}
+
+/// Formats the measurements as a table, comparing medians against `baseline`.
+pub fn report(measurements: &[Measurement], baseline: Option<&Baseline>) -> String {
+    let mut out = format!("{:<4} {:<6} {:>10} {:>10} {:>10} {:>12} {:>9}\n",
+        "Day", "Stage", "Min", "Median", "Max", "Throughput", "Change");
+    for m in measurements {
+        let change = baseline
+            .and_then(|b| b.get(&(m.day, m.stage.to_string())))
+            .map(|old| {
+                let ratio = m.timing.median.as_secs_f64() / old.as_secs_f64().max(f64::MIN_POSITIVE);
+                format!("{:+.1}%", (ratio - 1.0) * 100.0)
+            })
+            .unwrap_or_default();
+        let line = format!("{:<4} {:<6} {:>10} {:>10} {:>10} {:>9.1}MB/s {:>9}",
+            format!("{:02}", m.day), m.stage,
+            format!("{:.1?}", m.timing.min),
+            format!("{:.1?}", m.timing.median),
+            format!("{:.1?}", m.timing.max),
+            m.timing.throughput(m.input_len) / 1e6,
+            change);
+        out += line.trim_end();
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing() {
+        let mut calls = 0;
+        let timing = Timing::measure(5, || calls += 1);
+        assert_eq!(calls, 5);
+        assert!(timing.min <= timing.median && timing.median <= timing.max);
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-baseline-{}.txt", std::process::id()));
+        let timing = Timing { min: Duration::from_nanos(1), median: Duration::from_nanos(20), max: Duration::from_nanos(300) };
+        let measurements = [Measurement { day: 3, stage: "part2", input_len: 10, timing }];
+        save_baseline(&path, &measurements).unwrap();
+        let baseline = load_baseline(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(baseline.get(&(3, "part2".to_string())), Some(&Duration::from_nanos(20)));
+        assert!(report(&measurements, Some(&baseline)).contains("+0.0%"));
+    }
+}
@@ -0,0 +1,381 @@
+//! Client for downloading puzzle inputs and submitting answers.
+//!
+//! Inputs are cached as `dayNN/input.txt`, so each one is only downloaded once.
+//! Submissions are logged to `submissions.txt` so that repeated answers, answers
+//! already known to be too high or too low, and the site's rate limit are all
+//! handled locally without another request.
+
+pub mod mock;
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const YEAR: u32 = 2021;
+const USER_AGENT: &str = "github.com/james-lomax/AdventOfCode2021 aoc client";
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// No session cookie was configured (set `AOC_SESSION`).
+    NoSession,
+    Http(String),
+    /// The site answered with an unexpected status code.
+    Status(u16, String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(f, "No session cookie, set AOC_SESSION"),
+            ClientError::Http(e) => write!(f, "Request failed: {}", e),
+            ClientError::Status(code, body) => write!(f, "Unexpected status {}: {}", code, body.trim()),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<std::io::Error> for ClientError {
+    fn from(e: std::io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        ClientError::Http(e.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooLow,
+    TooHigh,
+    Incorrect,
+    /// Submitted too recently, try again after the given wait.
+    RateLimited(Duration),
+    /// A different answer was already accepted for this part.
+    AlreadySolved,
+}
+
+impl Outcome {
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooLow => "too_low",
+            Outcome::TooHigh => "too_high",
+            Outcome::Incorrect => "incorrect",
+            Outcome::RateLimited(_) => "rate_limited",
+            Outcome::AlreadySolved => "already_solved",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Outcome::Correct),
+            "too_low" => Some(Outcome::TooLow),
+            "too_high" => Some(Outcome::TooHigh),
+            "incorrect" => Some(Outcome::Incorrect),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer!"),
+            Outcome::TooLow => write!(f, "That's not the right answer, it's too low"),
+            Outcome::TooHigh => write!(f, "That's not the right answer, it's too high"),
+            Outcome::Incorrect => write!(f, "That's not the right answer"),
+            Outcome::RateLimited(wait) => write!(f, "Submitted too recently, wait {}s", wait.as_secs()),
+            Outcome::AlreadySolved => write!(f, "Already solved with a different answer"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Submission {
+    day: u32,
+    part: u32,
+    outcome: Outcome,
+    answer: String,
+}
+
+/// Past submissions and the time (in unix seconds) before which the site
+/// won't accept another answer.
+#[derive(Debug, Default)]
+struct History {
+    submissions: Vec<Submission>,
+    wait_until: u64,
+}
+
+impl History {
+    fn load(path: &std::path::Path) -> Self {
+        let mut history = History::default();
+        let contents = std::fs::read_to_string(path).unwrap_or_default();
+        for line in crate::parse::lines(&contents) {
+            let fields = line.split_whitespace().collect::<Vec<&str>>();
+            match fields[..] {
+                ["wait_until", secs] => history.wait_until = secs.parse().unwrap_or(0),
+                [day, part, outcome, answer] => {
+                    if let (Ok(day), Ok(part), Some(outcome)) = (day.parse(), part.parse(), Outcome::from_name(outcome)) {
+                        history.submissions.push(Submission { day, part, outcome, answer: answer.to_string() });
+                    }
+                }
+                _ => (),
+            }
+        }
+        history
+    }
+
+    fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let mut contents = format!("wait_until {}\n", self.wait_until);
+        for s in self.submissions.iter() {
+            contents += &format!("{} {} {} {}\n", s.day, s.part, s.outcome.name(), s.answer);
+        }
+        std::fs::write(path, contents)
+    }
+
+    // The outcome of `answer` if it can be decided without asking the site
+    fn known_outcome(&self, day: u32, part: u32, answer: &str) -> Option<Outcome> {
+        let previous = self.submissions.iter().filter(|s| s.day == day && s.part == part);
+        let value = answer.parse::<i64>().ok();
+        for s in previous {
+            let bound = s.answer.parse::<i64>().ok();
+            match s.outcome {
+                _ if s.answer == answer => return Some(s.outcome),
+                Outcome::Correct => return Some(Outcome::AlreadySolved),
+                Outcome::TooLow if value.is_some() && value <= bound => return Some(Outcome::TooLow),
+                Outcome::TooHigh if value.is_some() && value >= bound => return Some(Outcome::TooHigh),
+                _ => (),
+            }
+        }
+        None
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+    root: PathBuf,
+}
+
+impl Client {
+    /// Creates a client for the site at `base_url`, caching inputs and
+    /// submissions under `root`.
+    pub fn new(base_url: &str, session: Option<String>, root: impl Into<PathBuf>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .build()
+            .into();
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            root: root.into(),
+        }
+    }
+
+    /// Client configured from `AOC_BASE_URL` and `AOC_SESSION`, caching in the workspace.
+    pub fn from_env() -> Self {
+        let base_url = std::env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let session = std::env::var("AOC_SESSION").ok().filter(|s| !s.is_empty());
+        Self::new(&base_url, session, crate::runner::workspace_root())
+    }
+
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        self.root.join(format!("day{:02}", day)).join("input.txt")
+    }
+
+    fn history_path(&self) -> PathBuf {
+        self.root.join("submissions.txt")
+    }
+
+    fn cookie(&self) -> Result<String, ClientError> {
+        self.session.as_ref()
+            .map(|s| format!("session={}", s))
+            .ok_or(ClientError::NoSession)
+    }
+
+    /// Returns the input for `day`, downloading it first if it isn't cached.
+    pub fn fetch_input(&self, day: u32) -> Result<String, ClientError> {
+        let path = self.input_path(day);
+        if let Ok(contents) = std::fs::read_to_string(&path) {
+            return Ok(contents);
+        }
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        let mut response = self.agent.get(&url)
+            .header("Cookie", &self.cookie()?)
+            .header("User-Agent", USER_AGENT)
+            .call()?;
+        let body = response.body_mut().read_to_string()?;
+        if response.status() != 200 {
+            return Err(ClientError::Status(response.status().as_u16(), body));
+        }
+
+        std::fs::create_dir_all(path.parent().expect("input path has a day directory"))?;
+        std::fs::write(&path, &body)?;
+        Ok(body)
+    }
+
+    /// Submits `answer` for a day and part, unless its outcome is already
+    /// known from earlier submissions or the site is still rate limiting us.
+    pub fn submit(&self, day: u32, part: u32, answer: &str) -> Result<Outcome, ClientError> {
+        let mut history = History::load(&self.history_path());
+        if let Some(outcome) = history.known_outcome(day, part, answer) {
+            return Ok(outcome);
+        }
+        if history.wait_until > now() {
+            return Ok(Outcome::RateLimited(Duration::from_secs(history.wait_until - now())));
+        }
+
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        let mut response = self.agent.post(&url)
+            .header("Cookie", &self.cookie()?)
+            .header("User-Agent", USER_AGENT)
+            .send_form([("level", part.to_string()), ("answer", answer.to_string())])?;
+        let body = response.body_mut().read_to_string()?;
+        if response.status() != 200 {
+            return Err(ClientError::Status(response.status().as_u16(), body));
+        }
+
+        let outcome = parse_outcome(&body);
+        if let Some(wait) = parse_wait(&body) {
+            history.wait_until = now() + wait.as_secs();
+        }
+        if Outcome::from_name(outcome.name()).is_some() {
+            history.submissions.push(Submission { day, part, outcome, answer: answer.to_string() });
+        }
+        history.save(&self.history_path())?;
+        Ok(outcome)
+    }
+}
+
+fn parse_outcome(body: &str) -> Outcome {
+    if body.contains("That's the right answer") {
+        Outcome::Correct
+    } else if body.contains("answer too recently") {
+        Outcome::RateLimited(parse_wait(body).unwrap_or_default())
+    } else if body.contains("Did you already complete it") {
+        Outcome::AlreadySolved
+    } else if body.contains("your answer is too low") {
+        Outcome::TooLow
+    } else if body.contains("your answer is too high") {
+        Outcome::TooHigh
+    } else {
+        Outcome::Incorrect
+    }
+}
+
+// Finds how long the site wants us to wait, given either as
+// "You have 1m 5s left to wait" or "Please wait one minute before trying again"
+fn parse_wait(body: &str) -> Option<Duration> {
+    let words = body.split(|c: char| c.is_whitespace() || c == '.' || c == ';')
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<&str>>();
+    let number = |w: &str| w.parse::<u64>().ok().or(match w {
+        "one" => Some(1), "two" => Some(2), "three" => Some(3), "four" => Some(4), "five" => Some(5),
+        "ten" => Some(10), "fifteen" => Some(15), "thirty" => Some(30), "sixty" => Some(60),
+        _ => None,
+    });
+
+    if let Some(i) = words.windows(3).position(|w| w == ["left", "to", "wait"]) {
+        let secs = words[..i].iter().rev()
+            .take_while(|w| w.ends_with('s') || w.ends_with('m'))
+            .map(|w| {
+                let (n, unit) = w.split_at(w.len() - 1);
+                n.parse::<u64>().unwrap_or(0) * if unit == "m" { 60 } else { 1 }
+            })
+            .sum();
+        return Some(Duration::from_secs(secs));
+    }
+    words.windows(3)
+        .find(|w| w[0] == "wait" && w[2].starts_with("minute"))
+        .and_then(|w| number(w[1]))
+        .map(|minutes| Duration::from_secs(minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mock::MockServer;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        assert_eq!(parse_wait("You have 1m 5s left to wait."), Some(Duration::from_secs(65)));
+        assert_eq!(parse_wait("You have 36s left to wait."), Some(Duration::from_secs(36)));
+        assert_eq!(parse_wait("Please wait one minute before trying again."), Some(Duration::from_secs(60)));
+        assert_eq!(parse_wait("Please wait 5 minutes before trying again."), Some(Duration::from_secs(300)));
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let server = MockServer::start("secret");
+        server.set_input(1, "199\n200\n");
+        let root = temp_root("fetch");
+
+        let client = Client::new(&server.url(), Some("secret".to_string()), &root);
+        assert_eq!(client.fetch_input(1).unwrap(), "199\n200\n");
+        assert_eq!(std::fs::read_to_string(root.join("day01/input.txt")).unwrap(), "199\n200\n");
+        // Second fetch comes from the cache
+        assert_eq!(client.fetch_input(1).unwrap(), "199\n200\n");
+        assert_eq!(server.requests(), 1);
+
+        assert!(matches!(client.fetch_input(2), Err(ClientError::Status(404, _))));
+        let logged_out = Client::new(&server.url(), Some("wrong".to_string()), &root);
+        assert!(matches!(logged_out.fetch_input(3), Err(ClientError::Status(400, _))));
+        let no_session = Client::new(&server.url(), None, &root);
+        assert!(matches!(no_session.fetch_input(3), Err(ClientError::NoSession)));
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = MockServer::start("secret");
+        server.set_answer(1, 1, "7");
+        let root = temp_root("submit");
+        let client = Client::new(&server.url(), Some("secret".to_string()), &root);
+
+        assert_eq!(client.submit(1, 1, "5").unwrap(), Outcome::TooLow);
+        assert_eq!(server.requests(), 1);
+        // Known to be too low, or rate limited, without asking the site again
+        assert_eq!(client.submit(1, 1, "4").unwrap(), Outcome::TooLow);
+        assert!(matches!(client.submit(1, 1, "7").unwrap(), Outcome::RateLimited(wait) if wait.as_secs() > 50));
+        assert_eq!(server.requests(), 1);
+
+        // A client without our history hits the site's rate limit
+        let fresh_root = temp_root("fresh");
+        let fresh = Client::new(&server.url(), Some("secret".to_string()), &fresh_root);
+        assert!(matches!(fresh.submit(1, 1, "7").unwrap(), Outcome::RateLimited(wait) if wait.as_secs() > 50));
+        assert_eq!(server.requests(), 2);
+        std::fs::remove_dir_all(&fresh_root).unwrap();
+
+        server.clear_rate_limit();
+        std::fs::write(root.join("submissions.txt"), "wait_until 0\n1 1 too_low 5\n").unwrap();
+        assert_eq!(client.submit(1, 1, "7").unwrap(), Outcome::Correct);
+        assert_eq!(client.submit(1, 1, "7").unwrap(), Outcome::Correct);
+        assert_eq!(client.submit(1, 1, "8").unwrap(), Outcome::AlreadySolved);
+        assert_eq!(server.requests(), 3);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,203 @@
+//! Local stand-in for the Advent of Code site, for testing the client offline.
+//!
+//! Serves inputs and checks answers over plain HTTP on a loopback port, with the
+//! same responses (and one minute penalty for wrong answers) as the real site.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+const PENALTY: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct State {
+    inputs: HashMap<u32, String>,
+    answers: HashMap<(u32, u32), String>,
+    solved: HashSet<(u32, u32)>,
+    wait_until: Option<Instant>,
+    requests: usize,
+}
+
+pub struct MockServer {
+    port: u16,
+    session: String,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts serving on a free loopback port, accepting only `session` as the login cookie.
+    pub fn start(session: &str) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let port = listener.local_addr().expect("Mock server has no address").port();
+        let state = Arc::new(Mutex::new(State::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let cookie = format!("session={}", session);
+            let state = state.clone();
+            let shutdown = shutdown.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        // A broken connection only affects that request
+                        let _ = handle_connection(stream, &cookie, &state);
+                    }
+                }
+            })
+        };
+
+        Self { port, session: session.to_string(), state, shutdown, handle: Some(handle) }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    pub fn session(&self) -> &str {
+        &self.session
+    }
+
+    pub fn set_input(&self, day: u32, input: &str) {
+        self.state.lock().unwrap().inputs.insert(day, input.to_string());
+    }
+
+    pub fn set_answer(&self, day: u32, part: u32, answer: &str) {
+        self.state.lock().unwrap().answers.insert((day, part), answer.to_string());
+    }
+
+    /// Lifts the penalty after a wrong answer, instead of waiting it out.
+    pub fn clear_rate_limit(&self) {
+        self.state.lock().unwrap().wait_until = None;
+    }
+
+    /// Number of requests served so far.
+    pub fn requests(&self) -> usize {
+        self.state.lock().unwrap().requests
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the shutdown flag
+        let _ = TcpStream::connect(("127.0.0.1", self.port));
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    cookie: Option<String>,
+    body: String,
+}
+
+fn read_request(stream: &TcpStream) -> std::io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut fields = line.split_whitespace();
+    let method = fields.next().unwrap_or_default().to_string();
+    let path = fields.next().unwrap_or_default().to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "cookie" => cookie = Some(value.trim().to_string()),
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                _ => (),
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request { method, path, cookie, body: String::from_utf8_lossy(&body).into_owned() })
+}
+
+fn handle_connection(mut stream: TcpStream, cookie: &str, state: &Mutex<State>) -> std::io::Result<()> {
+    let request = read_request(&stream)?;
+    let (status, body) = respond(&request, cookie, &mut state.lock().unwrap());
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+    write!(stream, "HTTP/1.1 {} {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, reason, body.len(), body)?;
+    stream.flush()
+}
+
+// Paths look like /2021/day/5/input and /2021/day/5/answer
+fn respond(request: &Request, cookie: &str, state: &mut State) -> (u16, String) {
+    state.requests += 1;
+    let segments = request.path.trim_matches('/').split('/').collect::<Vec<&str>>();
+    let (day, action) = match segments[..] {
+        ["2021", "day", day, action] => match day.parse::<u32>() {
+            Ok(day) => (day, action),
+            Err(_) => return (404, "404 Not Found".to_string()),
+        },
+        _ => return (404, "404 Not Found".to_string()),
+    };
+    if request.cookie.as_deref() != Some(cookie) {
+        return (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.".to_string());
+    }
+
+    match (request.method.as_str(), action) {
+        ("GET", "input") => match state.inputs.get(&day) {
+            Some(input) => (200, input.clone()),
+            None => (404, "404 Not Found".to_string()),
+        },
+        ("POST", "answer") => (200, format!("<main><article><p>{}</p></article></main>", check_answer(request, day, state))),
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
+
+fn check_answer(request: &Request, day: u32, state: &mut State) -> String {
+    let form = request.body.split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .collect::<HashMap<&str, &str>>();
+    let part = form.get("level").and_then(|l| l.parse::<u32>().ok()).unwrap_or(0);
+    let answer = form.get("answer").copied().unwrap_or_default();
+
+    if let Some(wait) = state.wait_until.and_then(|t| t.checked_duration_since(Instant::now())) {
+        let secs = wait.as_secs() + 1;
+        let left = if secs >= 60 { format!("{}m {}s", secs / 60, secs % 60) } else { format!("{}s", secs) };
+        return format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {} left to wait.", left);
+    }
+    if state.solved.contains(&(day, part)) {
+        return "You don't seem to be solving the right level.  Did you already complete it?".to_string();
+    }
+    let Some(expected) = state.answers.get(&(day, part)) else {
+        return "You don't seem to be solving the right level.  Did you already complete it?".to_string();
+    };
+
+    if answer == expected {
+        state.solved.insert((day, part));
+        return "That's the right answer!  You are one gold star closer to saving your vacation.".to_string();
+    }
+    state.wait_until = Some(Instant::now() + PENALTY);
+    let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+        (Ok(a), Ok(e)) if a < e => "; your answer is too low",
+        (Ok(a), Ok(e)) if a > e => "; your answer is too high",
+        _ => "",
+    };
+    format!("That's not the right answer{}.  Please wait one minute before trying again.", hint)
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+/// Error in a puzzle input, located by line and column (both 1-based).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending part of the input.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Error at `fragment`, which must be a slice of `source`.
+    pub fn at(source: &str, fragment: &str, message: impl Into<String>) -> Self {
+        let offset = (fragment.as_ptr() as usize).checked_sub(source.as_ptr() as usize)
+            .filter(|offset| offset + fragment.len() <= source.len())
+            .expect("Error fragment is not part of the parsed source");
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: fragment.to_string(),
+            message: message.into(),
+        }
+    }
+
+    /// Error at the end of `source`, for input that stops too early.
+    pub fn at_end(source: &str, message: impl Into<String>) -> Self {
+        Self::at(source, &source[source.len()..], message)
+    }
+
+    /// Renders the error compiler-style, quoting the offending line of `source`.
+    pub fn render(&self, source: &str, path: &str) -> String {
+        let line = source.lines().nth(self.line - 1).unwrap_or("");
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = "^".repeat(self.text.lines().next().map_or(0, |t| t.chars().count()).max(1));
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}\n",
+            self.message,
+            gutter, path, self.line, self.column,
+            gutter,
+            self.line, line,
+            gutter, " ".repeat(self.column - 1), underline
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_location() {
+        let source = "00100\n11210\n10110";
+        let err = ParseError::at(source, &source[8..9], "Unexpected character '2'");
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "2"));
+        assert_eq!(err.to_string(), "2:3: Unexpected character '2'");
+        assert_eq!(
+            err.render(source, "input.txt"),
+            "error: Unexpected character '2'\n --> input.txt:2:3\n  |\n2 | 11210\n  |   ^\n"
+        );
+
+        let end = ParseError::at_end(source, "Unexpected end of input");
+        assert_eq!((end.line, end.column), (3, 6));
+    }
+}
@@ -0,0 +1,86 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use crate::{Answer, Solution};
+
+/// Random generator of valid puzzle inputs, for stress testing and benchmarking
+/// the solvers on inputs bigger than the real one.
+pub trait Generator {
+    /// Fewest records a valid input has, for days which can't parse an empty one.
+    const MIN_SIZE: usize = 0;
+
+    /// Generates an input with `size` records (depths, commands, cards, ...),
+    /// which must be at least [`Generator::MIN_SIZE`].
+    fn generate(rng: &mut StdRng, size: usize) -> String;
+}
+
+/// Generates an input for `G` from a seed, so runs can be reproduced.
+pub fn generate<G: Generator>(seed: u64, size: usize) -> String {
+    G::generate(&mut StdRng::seed_from_u64(seed), size)
+}
+
+/// Checks that `S` generates the same input for the same seed, different ones
+/// for different seeds, and only inputs it can parse and answer, empty ones
+/// included unless below its [`Generator::MIN_SIZE`]. Panics with the seed and
+/// size that failed, for the days' tests.
+pub fn assert_generates<S: Solution + Generator>() {
+    for seed in 0..10 {
+        for size in [0, 1, 50].into_iter().filter(|size| *size >= S::MIN_SIZE) {
+            let input = generate::<S>(seed, size);
+            assert_eq!(input, generate::<S>(seed, size), "seed {} size {} generated different inputs", seed, size);
+            let name = format!("<seed {} size {}>", seed, size);
+            let parsed = S::parse(&input).unwrap_or_else(|e| panic!("generated input didn't parse\n{}", e.render(&input, &name)));
+            if let Err(e) = S::part1(&parsed).into_answer().and(S::part2(&parsed).into_answer()) {
+                panic!("{} has no answer: {}", name, e);
+            }
+        }
+        assert_ne!(generate::<S>(seed, 50), generate::<S>(seed + 1, 50), "seeds {} and {} generated the same input", seed, seed + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    struct Digits;
+
+    impl Generator for Digits {
+        fn generate(rng: &mut StdRng, size: usize) -> String {
+            (0..size).map(|_| char::from(b'0' + rng.random_range(0..10))).collect()
+        }
+    }
+
+    impl Solution for Digits {
+        const DAY: u32 = 1;
+
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = Result<u32, &'static str>;
+
+        fn parse(input: &str) -> Result<Self::Input, crate::ParseError> {
+            crate::parse::Span::new(input).digits(10)
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Answer2 {
+            input.iter().max().cloned().ok_or("no digits")
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "<seed 0 size 0> has no answer: no digits")]
+    fn test_assert_generates() {
+        assert_generates::<Digits>();
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate::<Digits>(7, 20), generate::<Digits>(7, 20));
+        assert_ne!(generate::<Digits>(7, 20), generate::<Digits>(8, 20));
+        assert_eq!(generate::<Digits>(7, 20).len(), 20);
+    }
+}
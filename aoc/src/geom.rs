@@ -0,0 +1,134 @@
+//! Integer 2D points, used for positions and directions on the puzzle grids.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point on the integer lattice, or the offset between two of them.
+/// Points are ordered by `x`, then `y`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// Alias for when a point is used as a direction or offset.
+pub type Vec2 = Point;
+
+impl Point {
+    pub const ZERO: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Signum of each component, e.g. the unit step of an axis-aligned or diagonal line.
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn abs(self) -> Self {
+        Self::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Taxicab distance, the number of 4-neighbour steps between the points.
+    pub fn manhattan(self, other: Point) -> i32 {
+        let d = (other - self).abs();
+        d.x + d.y
+    }
+
+    /// Chessboard distance, the number of 8-neighbour steps between the points.
+    pub fn chebyshev(self, other: Point) -> i32 {
+        let d = (other - self).abs();
+        d.x.max(d.y)
+    }
+
+    /// Every lattice point on the segment from `self` to `end`, both included.
+    pub fn line_to(self, end: Point) -> impl Iterator<Item = Point> {
+        let diff = end - self;
+        let steps = gcd(diff.x.abs(), diff.y.abs());
+        let step = if steps == 0 { Point::ZERO } else { Point::new(diff.x / steps, diff.y / steps) };
+        (0..=steps).map(move |i| self + step * i)
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, k: i32) -> Point {
+        Point::new(self.x * k, self.y * k)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point() {
+        let a = Point::new(1, -2);
+        let b = Point::new(4, 2);
+        assert_eq!(b - a, Point::new(3, 4));
+        assert_eq!((a - b).signum(), Point::new(-1, -1));
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert!(Point::new(0, 9) < Point::new(1, 0));
+
+        let line = |a: Point, b: Point| a.line_to(b).map(|p| (p.x, p.y)).collect::<Vec<_>>();
+        assert_eq!(line(Point::new(3, 4), Point::new(1, 4)), [(3, 4), (2, 4), (1, 4)]);
+        assert_eq!(line(Point::new(0, 0), Point::new(2, -2)), [(0, 0), (1, -1), (2, -2)]);
+        assert_eq!(line(Point::new(0, 0), Point::new(4, 2)), [(0, 0), (2, 1), (4, 2)]);
+        assert_eq!(line(a, a), [(1, -2)]);
+    }
+}
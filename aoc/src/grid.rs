@@ -0,0 +1,259 @@
+//! Rectangular grids of cells, indexed by `Point` with `x` across and `y` down.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::parse::Span;
+use crate::{ParseError, Point};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    // Row-major
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+        where T: Clone
+    {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// Grid from a list of rows, which must all have the same width.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let height = rows.len();
+        Some(Self { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    // Each line of `input` is a row, with cells parsed from the spans given by `cells`
+    fn parse_rows<'a, I, F>(input: Span<'a>, cells: impl Fn(Span<'a>) -> I, parse: F) -> Result<Self, ParseError>
+        where I: Iterator<Item = Span<'a>>, F: Fn(Span<'a>) -> Result<T, ParseError>
+    {
+        let mut width = None;
+        let mut grid = Self { width: 0, height: 0, cells: Vec::new() };
+        for line in input.lines() {
+            let row = cells(line).map(&parse).collect::<Result<Vec<T>, ParseError>>()?;
+            match width {
+                Some(width) if width != row.len() =>
+                    return Err(line.error(format!("Expected {} cells in row, got {}", width, row.len()))),
+                _ => width = Some(row.len()),
+            }
+            grid.cells.extend(row);
+            grid.height += 1;
+        }
+        grid.width = width.unwrap_or(0);
+        Ok(grid)
+    }
+
+    /// Parses whitespace separated values, one row per line, such as a bingo card.
+    pub fn parse_words(input: Span) -> Result<Self, ParseError>
+        where T: FromStr, T::Err: fmt::Display
+    {
+        Self::parse_rows(input, |line| line.words(), |s| s.number())
+    }
+
+    /// Parses one cell per character, one row per line, such as a height map.
+    pub fn parse_chars(input: Span, parse: impl Fn(char) -> Option<T>) -> Result<Self, ParseError> {
+        Self::parse_rows(input, |line| line.chars().map(|(_, s)| s), |s| {
+            let c = s.as_str().chars().next().unwrap();
+            parse(c).ok_or_else(|| s.error(format!("Unexpected character '{}'", c)))
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        (0..self.width as i32).contains(&pos.x) && (0..self.height as i32).contains(&pos.y)
+    }
+
+    fn offset(&self, pos: Point) -> Option<usize> {
+        self.contains(pos).then(|| pos.y as usize * self.width + pos.x as usize)
+    }
+
+    /// Cell at `pos`, or `None` if it is off the grid.
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.offset(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.offset(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// All cells in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width as i32;
+        (0..self.height as i32).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    /// Panics if `y` is outside the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {} is outside the grid", y);
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Panics if `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is outside the grid", x);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Cells from `start` onwards, moving by `step` until falling off the grid.
+    pub fn ray(&self, start: Point, step: Point) -> impl Iterator<Item = &T> {
+        std::iter::successors(Some(start), move |pos| Some(*pos + step))
+            .map_while(|pos| self.get(pos))
+    }
+
+    /// The diagonal from the top left corner.
+    pub fn diagonal(&self) -> impl Iterator<Item = &T> {
+        self.ray(Point::new(0, 0), Point::new(1, 1))
+    }
+
+    /// The diagonal from the top right corner.
+    pub fn anti_diagonal(&self) -> impl Iterator<Item = &T> {
+        self.ray(Point::new(self.width as i32 - 1, 0), Point::new(-1, 1))
+    }
+
+    fn neighbours<'a>(&self, pos: Point, steps: &'a [(i32, i32)]) -> impl Iterator<Item = Point> + 'a {
+        let (width, height) = (self.width as i32, self.height as i32);
+        steps.iter()
+            .map(move |&(x, y)| pos + Point::new(x, y))
+            .filter(move |p| (0..width).contains(&p.x) && (0..height).contains(&p.y))
+    }
+
+    /// Positions above, below, left and right of `pos` which are on the grid.
+    pub fn neighbours4(&self, pos: Point) -> impl Iterator<Item = Point> {
+        self.neighbours(pos, &[(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    /// Positions around `pos`, including diagonally, which are on the grid.
+    pub fn neighbours8(&self, pos: Point) -> impl Iterator<Item = Point> {
+        self.neighbours(pos, &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)])
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Self
+        where T: Clone
+    {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width).flat_map(|x| self.column(x).cloned()).collect(),
+        }
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate(&self) -> Self
+        where T: Clone
+    {
+        let mut rotated = self.transpose();
+        rotated.cells.chunks_mut(rotated.width.max(1)).for_each(|row| row.reverse());
+        rotated
+    }
+
+    /// Grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("({}, {}) is outside the grid", pos.x, pos.y))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        self.get_mut(pos).unwrap_or_else(|| panic!("({}, {}) is outside the grid", pos.x, pos.y))
+    }
+}
+
+// Cells are right aligned in columns separated by spaces. The alternate form
+// `{:#}` leaves out the spaces, for grids of single characters
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells = self.cells.iter().map(|c| c.to_string()).collect::<Vec<String>>();
+        let width = cells.iter().map(|c| c.chars().count()).max().unwrap_or(0);
+        let sep = if f.alternate() { "" } else { " " };
+        for row in cells.chunks(self.width.max(1)) {
+            let row = row.iter().map(|c| format!("{:>1$}", c, width)).collect::<Vec<String>>();
+            writeln!(f, "{}", row.join(sep))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let grid = Grid::<u32>::parse_words(Span::new("1  2 3\n4 5 6\n")).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point::new(2, 1)], 6);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), [&2, &5]);
+        assert_eq!(grid.diagonal().collect::<Vec<_>>(), [&1, &5]);
+        assert_eq!(grid.anti_diagonal().collect::<Vec<_>>(), [&3, &5]);
+        assert_eq!(grid.neighbours4(Point::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 5);
+
+        assert_eq!(grid.to_string(), "1 2 3\n4 5 6\n");
+        assert_eq!(grid.transpose().to_string(), "1 4\n2 5\n3 6\n");
+        assert_eq!(grid.rotate().to_string(), "4 1\n5 2\n6 3\n");
+        assert_eq!(grid.rotate().rotate().rotate().rotate(), grid);
+        assert_eq!(grid.map(|v| v * 2).to_string(), " 2  4  6\n 8 10 12\n");
+
+        let err = Grid::<u32>::parse_words(Span::new("1 2 3\n4 5\n")).unwrap_err();
+        assert_eq!((err.line, err.message.as_str()), (2, "Expected 3 cells in row, got 2"));
+
+        let map = Grid::parse_chars(Span::new("21\n39\n"), |c| c.to_digit(10)).unwrap();
+        assert_eq!(format!("{:#}", map), "21\n39\n");
+        let err = Grid::parse_chars(Span::new("21\n3x\n"), |c| c.to_digit(10)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 2 is outside the grid")]
+    fn test_column_outside() {
+        // Used to wrap round into the next row
+        Grid::new(2, 2, 0).column(2).count();
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 is outside the grid")]
+    fn test_row_outside() {
+        Grid::new(2, 2, 0).row(2);
+    }
+}
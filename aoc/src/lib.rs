@@ -0,0 +1,22 @@
+//! Shared helpers for the Advent of Code 2021 solutions.
+
+pub mod bench;
+pub mod client;
+pub mod error;
+pub mod gen;
+pub mod geom;
+pub mod grid;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod solution;
+pub mod stream;
+pub mod trace;
+pub mod verify;
+pub mod vis;
+
+pub use error::ParseError;
+pub use geom::{Point, Vec2};
+pub use grid::Grid;
+pub use runner::{run, run_streaming};
+pub use solution::{Answer, Day, Solution};
@@ -0,0 +1,267 @@
+//! Line-oriented parsing of puzzle inputs.
+//!
+//! Inputs are taken apart as [`Span`]s, which remember where in the input they
+//! came from, so any piece can report a [`ParseError`] pointing at itself.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// Splits `input` on `sep`, trimming each field and skipping empty ones.
+pub fn fields(input: &str, sep: char) -> impl Iterator<Item = &str> {
+    input.split(sep).map(|s| s.trim()).filter(|s| !s.is_empty())
+}
+
+/// Iterates over the trimmed, non-blank lines of `input`.
+pub fn lines(input: &str) -> impl Iterator<Item = &str> {
+    fields(input, '\n')
+}
+
+/// Iterates over the trimmed, non-empty blocks of `input` separated by blank lines.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    input.split("\n\n").map(|s| s.trim()).filter(|s| !s.is_empty())
+}
+
+/// A piece of a puzzle input, along with its position in the whole input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    source: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Span<'a> {
+    /// Span covering all of `source`.
+    pub fn new(source: &'a str) -> Self {
+        Self { source, start: 0, end: source.len() }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        &self.source[self.start..self.end]
+    }
+
+    /// Byte offset of the span in the whole input.
+    pub fn offset(&self) -> usize {
+        self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    // Span of `text`, which must be a slice of this span
+    fn slice(&self, text: &'a str) -> Span<'a> {
+        let start = text.as_ptr() as usize - self.source.as_ptr() as usize;
+        debug_assert!(start >= self.start && start + text.len() <= self.end);
+        Span { source: self.source, start, end: start + text.len() }
+    }
+
+    /// Empty span at the end of this one, for input that stops too early.
+    pub fn at_end(&self) -> Span<'a> {
+        Span { start: self.end, ..*self }
+    }
+
+    /// Error pointing at this span.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::at(self.source, self.as_str(), message)
+    }
+
+    /// Splits on `sep`, trimming each field and skipping empty ones.
+    pub fn fields(&self, sep: char) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        fields(self.as_str(), sep).map(move |s| span.slice(s))
+    }
+
+    /// The trimmed, non-blank lines.
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        self.fields('\n')
+    }
+
+    /// The trimmed blocks of lines separated by blank lines.
+    pub fn blocks(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        blocks(self.as_str()).map(move |s| span.slice(s))
+    }
+
+    /// The whitespace separated words.
+    pub fn words(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        self.as_str().split_whitespace().map(move |s| span.slice(s))
+    }
+
+    /// Each character along with its span.
+    pub fn chars(&self) -> impl Iterator<Item = (char, Span<'a>)> + 'a {
+        let span = *self;
+        self.as_str().char_indices()
+            .map(move |(i, c)| (c, span.slice(&span.as_str()[i..i + c.len_utf8()])))
+    }
+
+    /// Parses the whole span as a number.
+    pub fn number<T>(&self) -> Result<T, ParseError>
+        where T: FromStr, T::Err: Display
+    {
+        self.as_str().parse::<T>()
+            .map_err(|e| self.error(format!("Invalid number '{}' ({})", self.as_str(), e)))
+    }
+
+    /// Parses a list of numbers separated by `sep`, such as `3,4,3,1,2`.
+    pub fn list<T>(&self, sep: char) -> Result<Vec<T>, ParseError>
+        where T: FromStr, T::Err: Display
+    {
+        self.fields(sep).map(|s| s.number()).collect()
+    }
+
+    /// Parses every character as a digit in the given radix, such as `10110`.
+    pub fn digits<T: From<u8>>(&self, radix: u32) -> Result<Vec<T>, ParseError> {
+        self.chars()
+            .map(|(c, span)| c.to_digit(radix)
+                .map(|d| T::from(d as u8))
+                .ok_or_else(|| span.error(format!("Unexpected character '{}' (expected base {} digit)", c, radix))))
+            .collect()
+    }
+
+    /// Parses each line as a row of digits. All rows must have the same width.
+    pub fn digit_rows<T: From<u8>>(&self, radix: u32) -> Result<Vec<Vec<T>>, ParseError> {
+        let mut width = None;
+        self.lines()
+            .map(|line| {
+                let row = line.digits(radix)?;
+                match width {
+                    Some(width) if width != row.len() =>
+                        Err(line.error(format!("Expected {} digits, got {}", width, row.len()))),
+                    _ => {
+                        width = Some(row.len());
+                        Ok(row)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Every integer in the span, skipping any other text. A `-` directly
+    /// before the digits makes the number negative.
+    pub fn numbers(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        let text = self.as_str();
+        let bytes = text.as_bytes();
+        let mut i = 0;
+        std::iter::from_fn(move || {
+            while i < bytes.len() && !bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            if i == bytes.len() {
+                return None;
+            }
+            let start = if i > 0 && bytes[i - 1] == b'-' { i - 1 } else { i };
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            Some(span.slice(&text[start..i]))
+        })
+    }
+
+    /// Extracts exactly as many numbers as the tuple has fields, ignoring any
+    /// other text, e.g. `(x1, y1, x2, y2)` from `0,9 -> 5,9`.
+    pub fn tuple<T: FromNumbers>(&self) -> Result<T, ParseError> {
+        let numbers = self.numbers().collect::<Vec<Span>>();
+        if numbers.len() != T::LEN {
+            return Err(self.error(format!("Expected {} numbers, found {}", T::LEN, numbers.len())));
+        }
+        T::from_numbers(&numbers)
+    }
+}
+
+/// Tuples which [`Span::tuple`] can extract.
+pub trait FromNumbers: Sized {
+    const LEN: usize;
+
+    fn from_numbers(numbers: &[Span]) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_from_numbers {
+    ($len:expr; $($t:ident $i:tt),+) => {
+        impl<$($t),+> FromNumbers for ($($t,)+)
+            where $($t: FromStr, $t::Err: Display),+
+        {
+            const LEN: usize = $len;
+
+            fn from_numbers(numbers: &[Span]) -> Result<Self, ParseError> {
+                Ok(($(numbers[$i].number::<$t>()?,)+))
+            }
+        }
+    };
+}
+
+impl_from_numbers!(1; A 0);
+impl_from_numbers!(2; A 0, B 1);
+impl_from_numbers!(3; A 0, B 1, C 2);
+impl_from_numbers!(4; A 0, B 1, C 2, D 3);
+impl_from_numbers!(5; A 0, B 1, C 2, D 3, E 4);
+impl_from_numbers!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let sample = "199
+            200
+
+            208
+            ";
+        assert_eq!(lines(sample).collect::<Vec<_>>(), ["199", "200", "208"]);
+        assert_eq!(fields(" 3,4 ,,3\n", ',').collect::<Vec<_>>(), ["3", "4", "3"]);
+    }
+
+    #[test]
+    fn test_spans() {
+        let source = "7,4,9\n\n22 13\n 8  2\n";
+        let blocks = Span::new(source).blocks().collect::<Vec<Span>>();
+        assert_eq!(blocks.iter().map(|b| b.as_str()).collect::<Vec<_>>(), ["7,4,9", "22 13\n 8  2"]);
+        assert_eq!(blocks[0].list::<u32>(',').unwrap(), [7, 4, 9]);
+
+        let rows = blocks[1].lines().collect::<Vec<Span>>();
+        assert_eq!(rows[1].as_str(), "8  2");
+        let words = rows[1].words().collect::<Vec<Span>>();
+        assert_eq!(words[1].as_str(), "2");
+        let err = words[1].error("Bad");
+        assert_eq!((err.line, err.column), (4, 5));
+        assert_eq!(blocks[1].at_end().error("").column, 6);
+    }
+
+    #[test]
+    fn test_number() {
+        let span = Span::new("12,x4");
+        let fields = span.fields(',').collect::<Vec<Span>>();
+        assert_eq!(fields[0].number::<u32>(), Ok(12));
+        let err = fields[1].number::<u32>().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 4, "x4"));
+        assert_eq!(err.message, "Invalid number 'x4' (invalid digit found in string)");
+        assert_eq!(span.list::<u32>(',').unwrap_err(), err);
+    }
+
+    #[test]
+    fn test_digits() {
+        let span = Span::new("00100\n11110\n");
+        assert_eq!(span.digit_rows::<u8>(2).unwrap(), [[0, 0, 1, 0, 0], [1, 1, 1, 1, 0]]);
+
+        let err = Span::new("00100\n11210\n").digit_rows::<u8>(2).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "2"));
+        let err = Span::new("00100\n1110\n").digit_rows::<u8>(2).unwrap_err();
+        assert_eq!((err.line, err.column, err.message.as_str()), (2, 1, "Expected 5 digits, got 4"));
+        assert_eq!(Span::new("9a").digits::<u32>(16).unwrap(), [9, 10]);
+    }
+
+    #[test]
+    fn test_tuple() {
+        let span = Span::new("0,9 -> 5,-9");
+        assert_eq!(span.numbers().map(|s| s.as_str()).collect::<Vec<_>>(), ["0", "9", "5", "-9"]);
+        assert_eq!(span.tuple::<(i32, i32, i32, i32)>(), Ok((0, 9, 5, -9)));
+        assert_eq!(span.tuple::<(i32, u8)>().unwrap_err().message, "Expected 2 numbers, found 4");
+        assert_eq!(Span::new("1 -> 2").tuple::<(u8, u8)>(), Ok((1, 2)));
+        let err = Span::new("1 -> 300").tuple::<(u8, u8)>().unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (6, "300"));
+    }
+}
@@ -0,0 +1,320 @@
+use std::any::Any;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::solution::Parsed;
+use crate::stream::StreamError;
+use crate::{trace, Day, ParseError};
+
+/// Outcome of running one part of a day, in a form other tools can consume.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    pub answer: Option<String>,
+    pub timings: Timings,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Timings {
+    /// Time to parse the input, which is shared by both parts.
+    pub parse_ns: u64,
+    pub solve_ns: u64,
+}
+
+fn nanos(d: Duration) -> u64 {
+    d.as_nanos().try_into().unwrap_or(u64::MAX)
+}
+
+impl PartResult {
+    /// Results for `parts` of a day that couldn't be run at all.
+    pub fn failed(day: u32, parts: RangeInclusive<u32>, error: &str) -> Vec<Self> {
+        parts
+            .map(|part| PartResult { day, part, answer: None, timings: Timings::default(), error: Some(error.to_string()) })
+            .collect()
+    }
+
+    fn solved(day: u32, part: u32, answer: Result<String, String>, timings: Timings) -> Self {
+        let (answer, error) = match answer {
+            Ok(answer) => (Some(answer), None),
+            Err(e) => (None, Some(e)),
+        };
+        PartResult { day, part, answer, timings, error }
+    }
+}
+
+/// Parses `input` once and runs each of `parts` on it, timing every stage.
+pub fn solve_parts(day: &Day, input: &str, parts: RangeInclusive<u32>) -> Result<Vec<PartResult>, ParseError> {
+    let results = solve_each(day, input, parts, |part, parsed| (day.solve(part, parsed), ()))?;
+    Ok(results.into_iter().map(|(result, _)| result).collect())
+}
+
+/// Like [`solve_parts`], also collecting the steps each part traces.
+pub fn explain_parts(day: &Day, input: &str, parts: RangeInclusive<u32>) -> Result<Vec<(PartResult, Vec<String>)>, ParseError> {
+    solve_each(day, input, parts, |part, parsed| trace::capture(|| day.solve(part, parsed)))
+}
+
+fn solve_each<T>(day: &Day, input: &str, parts: RangeInclusive<u32>, solve: impl Fn(u32, &Parsed) -> (Result<String, String>, T)) -> Result<Vec<(PartResult, T)>, ParseError> {
+    let start = Instant::now();
+    let parsed = day.parse(input)?;
+    let parse_ns = nanos(start.elapsed());
+    Ok(parts
+        .map(|part| {
+            let start = Instant::now();
+            let (answer, extra) = solve(part, &parsed);
+            let timings = Timings { parse_ns, solve_ns: nanos(start.elapsed()) };
+            (PartResult::solved(day.day, part, answer, timings), extra)
+        })
+        .collect())
+}
+
+/// Solves `parts` in one pass over `reader`, if the day supports streaming.
+/// Parsing and solving are interleaved, so the whole pass counts as parse time.
+pub fn stream_parts(day: &Day, reader: &mut dyn BufRead, parts: RangeInclusive<u32>) -> Option<Result<Vec<PartResult>, StreamError>> {
+    let start = Instant::now();
+    let answers = day.stream(reader)?;
+    let timings = Timings { parse_ns: nanos(start.elapsed()), solve_ns: 0 };
+    Some(answers.map(|(part1, part2)| parts
+        .map(|part| {
+            let answer = if part == 1 { part1.clone() } else { part2.clone() };
+            PartResult::solved(day.day, part, answer, timings)
+        })
+        .collect()))
+}
+
+/// Runs `parts` of every day at once on a pool of worker threads, reading each
+/// day's input with `read`. A day whose input can't be read or parsed, or which
+/// panics, is reported as failed without stopping the others.
+pub fn run_parallel(days: &[Day], parts: RangeInclusive<u32>, read: impl Fn(u32) -> Result<String, String> + Sync) -> Vec<PartResult> {
+    let next = AtomicUsize::new(0);
+    let workers = thread::available_parallelism().map_or(1, |n| n.get()).min(days.len());
+    let mut results = thread::scope(|s| {
+        let handles = (0..workers)
+            .map(|_| s.spawn(|| {
+                let mut results = Vec::new();
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    results.extend(run_guarded(day, parts.clone(), &read));
+                }
+                results
+            }))
+            .collect::<Vec<_>>();
+        handles.into_iter()
+            .flat_map(|h| h.join().expect("panics are caught per day"))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|r| (r.day, r.part));
+    results
+}
+
+/// Like [`solve_parts`], but turns every failure, panics included, into a
+/// failed result. A panic in one part doesn't lose the other.
+fn run_guarded(day: &Day, parts: RangeInclusive<u32>, read: impl Fn(u32) -> Result<String, String>) -> Vec<PartResult> {
+    let input = match read(day.day) {
+        Ok(input) => input,
+        Err(e) => return PartResult::failed(day.day, parts, &e),
+    };
+    let start = Instant::now();
+    let parsed = match catch(|| day.parse(&input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => return PartResult::failed(day.day, parts, &e.to_string()),
+        Err(e) => return PartResult::failed(day.day, parts, &e),
+    };
+    let parse_ns = nanos(start.elapsed());
+    parts
+        .map(|part| {
+            let start = Instant::now();
+            let answer = catch(|| day.solve(part, &parsed)).and_then(|answer| answer);
+            let timings = Timings { parse_ns, solve_ns: nanos(start.elapsed()) };
+            PartResult::solved(day.day, part, answer, timings)
+        })
+        .collect()
+}
+
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| format!("panicked: {}", panic_message(&*payload)))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown panic"
+    }
+}
+
+/// How many of the slowest parts [`summary`] highlights.
+const SLOWEST: usize = 3;
+
+/// Formats results from [`run_parallel`] as a table with totals, marking the
+/// slowest parts. `wall` is how long the whole run took.
+pub fn summary(results: &[PartResult], wall: Duration) -> String {
+    let total = |r: &PartResult| r.timings.parse_ns + r.timings.solve_ns;
+    let mut by_time = results.iter().filter(|r| r.error.is_none()).collect::<Vec<_>>();
+    by_time.sort_by_key(|r| std::cmp::Reverse(total(r)));
+
+    let mut out = format!("{:<4} {:<4} {:<20} {:>10} {:>10}\n", "Day", "Part", "Answer", "Parse", "Solve");
+    for r in results {
+        let answer = match (&r.answer, &r.error) {
+            (_, Some(e)) => format!("FAILED: {}", e),
+            (Some(answer), None) => answer.clone(),
+            (None, None) => String::new(),
+        };
+        let rank = by_time.iter().take(SLOWEST).position(|s| std::ptr::eq(*s, r))
+            .map(|i| format!("<- slowest #{}", i + 1))
+            .unwrap_or_default();
+        let line = if r.error.is_some() {
+            format!("{:02}   {:<4} {}", r.day, r.part, answer)
+        } else {
+            format!("{:02}   {:<4} {:<20} {:>10} {:>10} {}", r.day, r.part, answer,
+                format!("{:.1?}", Duration::from_nanos(r.timings.parse_ns)),
+                format!("{:.1?}", Duration::from_nanos(r.timings.solve_ns)),
+                rank)
+        };
+        out += line.trim_end();
+        out.push('\n');
+    }
+
+    // Both parts of a day share one parse, so only count it once
+    let mut cpu_ns = results.iter().map(|r| r.timings.solve_ns).sum::<u64>();
+    let mut days = results.iter().map(|r| (r.day, r.timings.parse_ns)).collect::<Vec<_>>();
+    days.dedup_by_key(|(day, _)| *day);
+    cpu_ns += days.iter().map(|(_, parse_ns)| parse_ns).sum::<u64>();
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    out += &format!("{} days, {} parts ({} failed) in {:.1?} wall time, {:.1?} summed over days\n",
+        days.len(), results.len(), failed, wall, Duration::from_nanos(cpu_ns));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u32 = 1;
+
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            crate::parse::Span::new(input).lines().map(|s| s.number()).collect()
+        }
+
+        fn part1(input: &Self::Input) -> Self::Answer1 {
+            input.iter().inspect(|n| crate::trace!("add {}", n)).sum()
+        }
+
+        fn part2(input: &Self::Input) -> Self::Answer2 {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_solve_parts() {
+        let day = Day::of::<Sum>();
+        let results = solve_parts(&day, "1\n2\n3\n", 1..=2).unwrap();
+        assert_eq!(results.iter().map(|r| r.answer.as_deref()).collect::<Vec<_>>(), [Some("6"), Some("3")]);
+        assert_eq!(results[0].timings.parse_ns, results[1].timings.parse_ns);
+        assert_eq!(solve_parts(&day, "1\nx\n", 2..=2).unwrap_err().line, 2);
+        assert!(stream_parts(&day, &mut "1\n".as_bytes(), 1..=2).is_none());
+
+        let explained = explain_parts(&day, "1\n2\n", 1..=2).unwrap();
+        let steps = explained.iter().map(|(_, steps)| steps.join(", ")).collect::<Vec<_>>();
+        assert_eq!(steps, ["add 1, add 2", ""]);
+    }
+
+    struct Fails;
+
+    impl Solution for Fails {
+        const DAY: u32 = 4;
+
+        type Input = ();
+        type Answer1 = u32;
+        type Answer2 = Result<u32, &'static str>;
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Self::Answer1 {
+            1
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            Err("no single answer")
+        }
+    }
+
+    #[test]
+    fn test_failed_part() {
+        let results = solve_parts(&Day::of::<Fails>(), "", 1..=2).unwrap();
+        let outcomes = results.iter().map(|r| (r.answer.as_deref(), r.error.as_deref())).collect::<Vec<_>>();
+        assert_eq!(outcomes, [(Some("1"), None), (None, Some("no single answer"))]);
+    }
+
+    struct Panics;
+
+    impl Solution for Panics {
+        const DAY: u32 = 2;
+
+        type Input = ();
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> Self::Answer1 {
+            1
+        }
+
+        fn part2(_: &Self::Input) -> Self::Answer2 {
+            panic!("oops")
+        }
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let mut third = Day::of::<Sum>();
+        third.day = 3;
+        let days = [Day::of::<Sum>(), Day::of::<Panics>(), third];
+        let read = |day| if day == 3 { Err("no input".to_string()) } else { Ok("1\n2\n".to_string()) };
+        let results = run_parallel(&days, 1..=2, read);
+        let outcomes = results.iter()
+            .map(|r| (r.day, r.part, r.answer.as_deref(), r.error.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(outcomes, [
+            (1, 1, Some("3"), None),
+            (1, 2, Some("2"), None),
+            (2, 1, Some("1"), None),
+            (2, 2, None, Some("panicked: oops")),
+            (3, 1, None, Some("no input")),
+            (3, 2, None, Some("no input")),
+        ]);
+
+        let table = summary(&results, Duration::from_millis(5));
+        assert!(table.contains("FAILED: panicked: oops"));
+        assert!(table.contains("slowest #1"));
+        assert!(table.contains("3 days, 6 parts (3 failed) in 5.0ms"));
+    }
+
+    #[test]
+    fn test_json() {
+        let result = &PartResult::failed(8, 2..=2, "no input")[0];
+        assert_eq!(serde_json::to_string(result).unwrap(),
+            r#"{"day":8,"part":2,"answer":null,"timings":{"parse_ns":0,"solve_ns":0},"error":"no input"}"#);
+    }
+}
@@ -0,0 +1,68 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use crate::stream::{StreamError, Streaming};
+use crate::{Answer, Solution};
+
+/// Root of the workspace, where the `dayNN` directories live.
+pub fn workspace_root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().expect("aoc crate is inside the workspace")
+}
+
+/// Default puzzle input location for `day`.
+pub fn input_path(day: u32) -> PathBuf {
+    workspace_root().join(format!("day{:02}", day)).join("input.txt")
+}
+
+/// Reads puzzle input from a file, or from stdin when `path` is `-`.
+pub fn read_input(path: &Path) -> std::io::Result<String> {
+    if path == Path::new("-") {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        Ok(contents)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
+
+/// Opens puzzle input for reading a line at a time, from stdin when `path` is `-`.
+pub fn open_input(path: &Path) -> std::io::Result<Box<dyn BufRead>> {
+    if path == Path::new("-") {
+        Ok(Box::new(std::io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+/// Reads `input.txt` from the working directory and prints both answers of `S`.
+pub fn run<S: Solution>() {
+    let contents = std::fs::read_to_string("input.txt").expect("file error");
+    let input = S::parse(&contents).unwrap_or_else(|e| {
+        eprint!("{}", e.render(&contents, "input.txt"));
+        std::process::exit(1);
+    });
+    print_answer(1, S::part1(&input));
+    print_answer(2, S::part2(&input));
+}
+
+fn print_answer(part: u32, answer: impl Answer) {
+    match answer.into_answer() {
+        Ok(answer) => println!("Part {} = {}", part, answer),
+        Err(e) => println!("Part {} failed: {}", part, e),
+    }
+}
+
+/// Like [`run`], but streams `input.txt` a line at a time instead of loading it.
+pub fn run_streaming<S: Streaming>() {
+    let file = File::open("input.txt").expect("file error");
+    let (part1, part2) = S::stream(BufReader::new(file)).unwrap_or_else(|e| {
+        match e {
+            StreamError::Parse(e) => eprintln!("error: {}\n --> input.txt:{}:{}", e.message, e.line, e.column),
+            StreamError::Io(e) => eprintln!("error: Failed to read input.txt: {}", e),
+        }
+        std::process::exit(1);
+    });
+    print_answer(1, part1);
+    print_answer(2, part2);
+}
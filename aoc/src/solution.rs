@@ -0,0 +1,155 @@
+use std::any::Any;
+use std::fmt::Display;
+use std::io::BufRead;
+
+use rand::rngs::StdRng;
+
+use crate::gen::Generator;
+use crate::stream::{StreamError, Streaming};
+use crate::vis::{Sink, Visualize};
+use crate::ParseError;
+
+/// What a part returns: a number or string, or a `Result` of one for parts
+/// which can fail on input the parser accepted, when ruling that out while
+/// parsing would cost as much as solving.
+pub trait Answer {
+    /// The answer as text, or why there isn't one.
+    fn into_answer(self) -> Result<String, String>;
+}
+
+macro_rules! plain_answers {
+    ($($t:ty),*) => {$(
+        impl Answer for $t {
+            fn into_answer(self) -> Result<String, String> {
+                Ok(self.to_string())
+            }
+        }
+    )*};
+}
+
+plain_answers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String);
+
+impl<T: Display, E: Display> Answer for Result<T, E> {
+    fn into_answer(self) -> Result<String, String> {
+        self.map(|answer| answer.to_string()).map_err(|e| e.to_string())
+    }
+}
+
+/// A day's puzzle solution. The input is parsed once and shared by both parts.
+pub trait Solution {
+    /// Puzzle day number (1-25).
+    const DAY: u32;
+
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
+
+/// Parsed input of a type-erased [`Day`].
+pub struct Parsed(Box<dyn Any>);
+
+type PartAnswers = (Result<String, String>, Result<String, String>);
+type GenerateFn = fn(&mut StdRng, usize) -> String;
+type StreamFn = fn(&mut dyn BufRead) -> Result<PartAnswers, StreamError>;
+type VisualizeFn = fn(&Parsed, &mut dyn Sink) -> std::io::Result<()>;
+
+/// Type-erased [`Solution`], so the runner can hold every day in one list.
+pub struct Day {
+    pub day: u32,
+    parse: fn(&str) -> Result<Parsed, ParseError>,
+    part1: fn(&Parsed) -> Result<String, String>,
+    part2: fn(&Parsed) -> Result<String, String>,
+    // Fewest records the generator can make, and the generator
+    generate: Option<(usize, GenerateFn)>,
+    stream: Option<StreamFn>,
+    visualize: Option<VisualizeFn>,
+}
+
+impl Day {
+    pub fn of<S: Solution>() -> Self
+        where S::Input: 'static
+    {
+        Day {
+            day: S::DAY,
+            parse: |input| S::parse(input).map(|i| Parsed(Box::new(i))),
+            part1: |parsed| S::part1(parsed.get::<S>()).into_answer(),
+            part2: |parsed| S::part2(parsed.get::<S>()).into_answer(),
+            generate: None,
+            stream: None,
+            visualize: None,
+        }
+    }
+
+    /// Like [`Day::of`], for solutions that can also generate random inputs.
+    pub fn generated<S: Solution + Generator>() -> Self
+        where S::Input: 'static
+    {
+        Day { generate: Some((S::MIN_SIZE, S::generate)), ..Self::of::<S>() }
+    }
+
+    /// Adds streaming support, for solutions which can also be solved a line at a time.
+    pub fn streaming<S: Streaming>(self) -> Self {
+        Day {
+            stream: Some(|reader| S::stream(reader).map(|(a, b)| (a.into_answer(), b.into_answer()))),
+            ..self
+        }
+    }
+
+    /// Adds a visualization, for solutions which can show their simulation.
+    pub fn visualized<S: Visualize>(self) -> Self
+        where S::Input: 'static
+    {
+        Day { visualize: Some(|parsed, sink| S::visualize(parsed.get::<S>(), sink)), ..self }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// Generates a random input of `size` records, if this day has a generator,
+    /// or an error if a valid input needs more records.
+    pub fn generate(&self, seed: u64, size: usize) -> Option<Result<String, String>> {
+        self.generate.map(|(min_size, generate)| {
+            if size < min_size {
+                return Err(format!("Inputs need at least {} record(s), got {}", min_size, size));
+            }
+            Ok(generate(&mut rand::SeedableRng::seed_from_u64(seed), size))
+        })
+    }
+
+    pub fn streams(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Solves both parts in one pass over `reader`, if this day supports streaming.
+    pub fn stream(&self, reader: &mut dyn BufRead) -> Option<Result<PartAnswers, StreamError>> {
+        self.stream.map(|stream| stream(reader))
+    }
+
+    /// Sends the frames of this day's simulation of `parsed` to `sink`, if it has a visualization.
+    pub fn visualize(&self, parsed: &Parsed, sink: &mut dyn Sink) -> Option<std::io::Result<()>> {
+        self.visualize.map(|visualize| visualize(parsed, sink))
+    }
+
+    /// Runs the given part (1 or 2) on input parsed by this day, giving its
+    /// answer or why it failed.
+    pub fn solve(&self, part: u32, parsed: &Parsed) -> Result<String, String> {
+        match part {
+            1 => (self.part1)(parsed),
+            2 => (self.part2)(parsed),
+            _ => panic!("Invalid part {} (must be 1 or 2)", part),
+        }
+    }
+}
+
+impl Parsed {
+    fn get<S: Solution>(&self) -> &S::Input
+        where S::Input: 'static
+    {
+        self.0.downcast_ref().expect("Input was parsed by a different day")
+    }
+}
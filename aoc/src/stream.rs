@@ -0,0 +1,96 @@
+//! Solving from a reader a line at a time, for inputs too big to load into a `String`.
+
+use std::fmt;
+use std::io::{self, BufRead};
+
+use crate::parse::Span;
+use crate::{ParseError, Solution};
+
+/// A [`Solution`] which can also solve both parts in one pass over its input,
+/// in bounded memory.
+pub trait Streaming: Solution {
+    fn stream<R: BufRead>(reader: R) -> Result<(Self::Answer1, Self::Answer2), StreamError>;
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    /// Error located by its line in the whole input.
+    Parse(ParseError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "Failed to read input: {}", e),
+            StreamError::Parse(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> Self {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> Self {
+        StreamError::Parse(e)
+    }
+}
+
+/// Parses each non-blank line of `reader` with `parse`, reusing one buffer for
+/// every line. Errors are located by their line in the whole input.
+pub fn parse_lines<R, T, F>(mut reader: R, mut parse: F) -> impl Iterator<Item = Result<T, StreamError>>
+    where R: BufRead, F: FnMut(Span) -> Result<T, ParseError>
+{
+    let mut buf = String::new();
+    let mut line_number = 0;
+    std::iter::from_fn(move || loop {
+        buf.clear();
+        match reader.read_line(&mut buf) {
+            Ok(0) => return None,
+            Ok(_) => line_number += 1,
+            Err(e) => return Some(Err(e.into())),
+        }
+        // Blank lines are skipped, like `Span::lines`
+        if let Some(line) = Span::new(&buf).lines().next() {
+            return Some(parse(line).map_err(|e| ParseError { line: e.line + line_number - 1, ..e }.into()));
+        }
+    })
+}
+
+/// Hands `f` the values of `results` up to the first error, then returns that
+/// error, or `f`'s result if there wasn't one. Lets solvers written against
+/// plain iterators run on fallible streams.
+pub fn process_results<T, E, R>(results: impl Iterator<Item = Result<T, E>>, f: impl FnOnce(&mut dyn Iterator<Item = T>) -> R) -> Result<R, E> {
+    let mut error = None;
+    let mut values = results.map_while(|r| r.map_err(|e| error = Some(e)).ok());
+    let result = f(&mut values);
+    match error {
+        Some(e) => Err(e),
+        None => Ok(result),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let input = "1\n\n  2\n3\n";
+        let sum = process_results(parse_lines(input.as_bytes(), |s| s.number::<u32>()), |n| n.sum::<u32>());
+        assert_eq!(sum.unwrap(), 6);
+
+        let input = "1\n\n  x\n3\n";
+        let sum = process_results(parse_lines(input.as_bytes(), |s| s.number::<u32>()), |n| n.sum::<u32>());
+        match sum.unwrap_err() {
+            StreamError::Parse(e) => assert_eq!((e.line, e.column, e.text.as_str()), (3, 3, "x")),
+            e => panic!("Unexpected error {}", e),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! Step-by-step explanations from inside the solvers. Solvers call [`trace!`](crate::trace!)
+//! at interesting steps. It prints nothing unless tracing was turned on with
+//! [`enable`] or [`capture`], and its arguments are only evaluated when it is,
+//! so it costs one atomic load when off.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+// Number of places traces currently go to: stderr if enabled, plus every capture
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    static MUTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Turns printing traces to stderr on or off.
+pub fn enable(on: bool) {
+    if ENABLED.swap(on, Ordering::Relaxed) != on {
+        if on {
+            ACTIVE.fetch_add(1, Ordering::Relaxed);
+        } else {
+            ACTIVE.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[inline]
+pub fn enabled() -> bool {
+    ACTIVE.load(Ordering::Relaxed) > 0
+}
+
+#[cold]
+pub fn emit(args: fmt::Arguments) {
+    if MUTED.get() {
+        return;
+    }
+    let captured = CAPTURED.with_borrow_mut(|captured| captured.as_mut().map(|c| c.push(args.to_string())).is_some());
+    if !captured && ENABLED.load(Ordering::Relaxed) {
+        eprintln!("  {}", args);
+    }
+}
+
+/// Traces a step, with the arguments of `format!`.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit(format_args!($($arg)*));
+        }
+    };
+}
+
+// Puts back the outer capture when dropped, so a panicking `f` doesn't leave tracing on
+struct Capturing(Option<Vec<String>>);
+
+impl Drop for Capturing {
+    fn drop(&mut self) {
+        ACTIVE.fetch_sub(1, Ordering::Relaxed);
+        CAPTURED.set(self.0.take());
+    }
+}
+
+/// Runs `f` and collects what it traces on this thread instead of printing it.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    ACTIVE.fetch_add(1, Ordering::Relaxed);
+    let _outer = Capturing(CAPTURED.replace(Some(Vec::new())));
+    let result = f();
+    let lines = CAPTURED.take().unwrap_or_default();
+    (result, lines)
+}
+
+struct Muting(bool);
+
+impl Drop for Muting {
+    fn drop(&mut self) {
+        MUTED.set(self.0);
+    }
+}
+
+/// Runs `f` without tracing, for work that would repeat steps traced elsewhere.
+pub fn muted<T>(f: impl FnOnce() -> T) -> T {
+    let _outer = Muting(MUTED.replace(true));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(n: u32) -> u32 {
+        (0..n).inspect(|i| crate::trace!("step {}", i)).sum()
+    }
+
+    #[test]
+    fn test_capture() {
+        assert_eq!(capture(|| steps(3)), (3, vec!["step 0".to_string(), "step 1".to_string(), "step 2".to_string()]));
+        assert_eq!(capture(|| muted(|| steps(3))), (3, vec![]));
+        // Nested captures don't leak into each other
+        let ((_, inner), outer) = capture(|| {
+            crate::trace!("outer");
+            capture(|| steps(1))
+        });
+        assert_eq!((inner, outer), (vec!["step 0".to_string()], vec!["outer".to_string()]));
+    }
+
+    #[test]
+    fn test_panic() {
+        // A panic inside a capture or mute still puts back the outer one
+        let (_, lines) = capture(|| {
+            let _ = std::panic::catch_unwind(|| capture(|| { steps(1); panic!("inner") }));
+            let _ = std::panic::catch_unwind(|| muted(|| panic!("muted")));
+            crate::trace!("outer");
+        });
+        assert_eq!(lines, ["outer"]);
+        assert!(CAPTURED.with_borrow(|captured| captured.is_none()));
+    }
+}
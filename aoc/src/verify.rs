@@ -0,0 +1,108 @@
+use std::fmt;
+use std::path::Path;
+
+use crate::Day;
+
+/// Known answers, loaded from a TOML file with a `[dayNN]` table per day
+/// holding `part1` and `part2` values.
+pub struct Answers(toml::Table);
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        contents.parse::<toml::Table>()
+            .map(Answers)
+            .map_err(|e| format!("Invalid answers file {}: {}", path.display(), e))
+    }
+
+    /// The recorded answer for a day and part, if any. Integer and string
+    /// answers are both accepted.
+    pub fn get(&self, day: u32, part: u32) -> Option<String> {
+        match self.0.get(&format!("day{:02}", day))?.get(format!("part{}", part))? {
+            toml::Value::String(s) => Some(s.clone()),
+            toml::Value::Integer(n) => Some(n.to_string()),
+            other => Some(other.to_string()),
+        }
+    }
+}
+
+/// Default location of the answers file.
+pub fn answers_path() -> std::path::PathBuf {
+    crate::runner::workspace_root().join("answers.toml")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String, actual: String },
+    /// No recorded answer or no input to check it against.
+    Missing(String),
+}
+
+#[derive(Clone, Debug)]
+pub struct Check {
+    pub day: u32,
+    pub part: u32,
+    pub status: Status,
+}
+
+/// Runs both parts of `day` on `input` (if there is one) and compares them
+/// with the recorded answers.
+pub fn verify_day(day: &Day, input: Option<&str>, answers: &Answers) -> Vec<Check> {
+    let check = |part, status| Check { day: day.day, part, status };
+    let Some(input) = input else {
+        return (1..=2).map(|part| check(part, Status::Missing("no input".to_string()))).collect();
+    };
+    let parsed = day.parse(input);
+    (1..=2)
+        .map(|part| {
+            let Some(expected) = answers.get(day.day, part) else {
+                return check(part, Status::Missing("no recorded answer".to_string()));
+            };
+            let actual = match &parsed {
+                Ok(parsed) => day.solve(part, parsed).unwrap_or_else(|e| format!("error: {}", e)),
+                Err(e) => format!("parse error at {}", e),
+            };
+            if actual == expected {
+                check(part, Status::Pass)
+            } else {
+                check(part, Status::Fail { expected, actual })
+            }
+        })
+        .collect()
+}
+
+/// Verifies every day against its `input.txt` in the workspace.
+pub fn verify_all(days: &[Day], answers: &Answers) -> Vec<Check> {
+    days.iter()
+        .flat_map(|day| {
+            let input = crate::runner::read_input(&crate::runner::input_path(day.day)).ok();
+            verify_day(day, input.as_deref(), answers)
+        })
+        .collect()
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Day {:02} part {}: ", self.day, self.part)?;
+        match &self.status {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail { expected, actual } => write!(f, "FAIL (expected {}, got {})", expected, actual),
+            Status::Missing(reason) => write!(f, "missing ({})", reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answers() {
+        let answers = Answers("[day01]\npart1 = 7\npart2 = \"abc\"\n".parse().unwrap());
+        assert_eq!(answers.get(1, 1), Some("7".to_string()));
+        assert_eq!(answers.get(1, 2), Some("abc".to_string()));
+        assert_eq!(answers.get(2, 1), None);
+    }
+}
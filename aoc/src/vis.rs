@@ -0,0 +1,230 @@
+//! Watching simulations step by step. Solvers emit [`Frame`]s into a [`Sink`],
+//! which renders them to the terminal or to image files.
+
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::{Grid, Point, Solution};
+
+/// One snapshot of a simulation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub caption: String,
+    pub image: Image,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Image {
+    /// Cell intensities, where 0 is background.
+    Grid(Grid<u32>),
+    /// A labelled bar per value.
+    Chart(Vec<(String, u64)>),
+}
+
+/// Height of the grid a chart is drawn on, in cells.
+const CHART_HEIGHT: usize = 40;
+
+impl Image {
+    /// Draws a chart as a grid, with a bar every other column. Grids are left as they are.
+    pub fn to_grid(&self) -> Grid<u32> {
+        match self {
+            Image::Grid(grid) => grid.clone(),
+            Image::Chart(bars) => {
+                let max = bars.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1);
+                let mut grid = Grid::new((2 * bars.len()).saturating_sub(1).max(1), CHART_HEIGHT, 0);
+                for (i, (_, value)) in bars.iter().enumerate() {
+                    let height = (*value as u128 * CHART_HEIGHT as u128).div_ceil(max as u128) as usize;
+                    for y in CHART_HEIGHT - height..CHART_HEIGHT {
+                        grid[Point::new(2 * i as i32, y as i32)] = 1;
+                    }
+                }
+                grid
+            }
+        }
+    }
+}
+
+/// Somewhere to send frames.
+pub trait Sink {
+    fn frame(&mut self, frame: Frame) -> io::Result<()>;
+}
+
+/// Collects the frames, for tests.
+impl Sink for Vec<Frame> {
+    fn frame(&mut self, frame: Frame) -> io::Result<()> {
+        self.push(frame);
+        Ok(())
+    }
+}
+
+/// A [`Solution`] which can show its simulation as it runs.
+pub trait Visualize: Solution {
+    fn visualize(input: &Self::Input, sink: &mut dyn Sink) -> io::Result<()>;
+}
+
+// Darkest to brightest
+const RAMP: &[u8] = b".:-=+*#%@";
+
+/// Draws frames as text, clearing the screen before each one and pausing
+/// after it so it can be watched.
+pub struct Terminal<W> {
+    out: W,
+    /// Grids wider than this are shrunk by taking the brightest cell of each block.
+    pub columns: usize,
+    pub delay: Duration,
+    /// Whether to clear the screen between frames.
+    pub clear: bool,
+}
+
+impl<W: Write> Terminal<W> {
+    pub fn new(out: W) -> Self {
+        Terminal { out, columns: 100, delay: Duration::from_millis(50), clear: true }
+    }
+
+    fn draw_grid(&mut self, grid: &Grid<u32>) -> io::Result<()> {
+        let block = grid.width().div_ceil(self.columns.max(1)).max(1);
+        let max = grid.iter().copied().max().unwrap_or(0).max(1);
+        for y in (0..grid.height()).step_by(block) {
+            let line = (0..grid.width()).step_by(block)
+                .map(|x| {
+                    let brightest = (y..(y + block).min(grid.height()))
+                        .flat_map(|y| grid.row(y)[x..(x + block).min(grid.width())].iter())
+                        .copied()
+                        .max()
+                        .unwrap_or(0);
+                    match brightest {
+                        0 => ' ',
+                        v => RAMP[(v as u64 * (RAMP.len() - 1) as u64 / max as u64) as usize] as char,
+                    }
+                })
+                .collect::<String>();
+            writeln!(self.out, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+
+    fn draw_chart(&mut self, bars: &[(String, u64)]) -> io::Result<()> {
+        let label_width = bars.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let max = bars.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1);
+        for (label, value) in bars {
+            let length = (*value as u128 * self.columns as u128 / max as u128) as usize;
+            writeln!(self.out, "{:>w$} |{} {}", label, "#".repeat(length), value, w = label_width)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Sink for Terminal<W> {
+    fn frame(&mut self, frame: Frame) -> io::Result<()> {
+        if self.clear {
+            write!(self.out, "\x1b[2J\x1b[H")?;
+        }
+        writeln!(self.out, "{}", frame.caption)?;
+        match &frame.image {
+            Image::Grid(grid) => self.draw_grid(grid)?,
+            Image::Chart(bars) => self.draw_chart(bars)?,
+        }
+        self.out.flush()?;
+        std::thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+/// Writes frames as binary PPM images, one after the other. Netpbm allows
+/// several images in one file, so the result can be turned into an animation
+/// with e.g. `ffmpeg -f ppm_pipe -i frames.ppm frames.gif`.
+pub struct Ppm<W> {
+    out: W,
+}
+
+/// Small grids are scaled up to about this many pixels across.
+const PPM_SIZE: usize = 400;
+
+impl<W: Write> Ppm<W> {
+    pub fn new(out: W) -> Self {
+        Ppm { out }
+    }
+}
+
+// Background is black, then dark blue to yellow with intensity
+fn colour(value: u32, max: u32) -> [u8; 3] {
+    if value == 0 {
+        return [0, 0, 0];
+    }
+    let t = value as f64 / max as f64;
+    let lerp = |from: f64, to: f64| (from + (to - from) * t).round() as u8;
+    [lerp(40.0, 255.0), lerp(40.0, 230.0), lerp(160.0, 60.0)]
+}
+
+impl<W: Write> Sink for Ppm<W> {
+    fn frame(&mut self, frame: Frame) -> io::Result<()> {
+        let grid = frame.image.to_grid();
+        let scale = (PPM_SIZE / grid.width().max(grid.height())).max(1);
+        let max = grid.iter().copied().max().unwrap_or(0).max(1);
+        write!(self.out, "P6\n{} {}\n255\n", grid.width() * scale, grid.height() * scale)?;
+        for row in grid.rows() {
+            let pixels = row.iter()
+                .flat_map(|v| std::iter::repeat_n(colour(*v, max), scale))
+                .flatten()
+                .collect::<Vec<u8>>();
+            for _ in 0..scale {
+                self.out.write_all(&pixels)?;
+            }
+        }
+        self.out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_frame() -> Frame {
+        let grid = Grid::from_rows(vec![vec![0, 1, 0], vec![2, 0, 4]]).unwrap();
+        Frame { caption: "Step 1".to_string(), image: Image::Grid(grid) }
+    }
+
+    #[test]
+    fn test_terminal() {
+        let mut out = Vec::new();
+        let mut terminal = Terminal { columns: 10, delay: Duration::ZERO, clear: false, ..Terminal::new(&mut out) };
+        terminal.frame(grid_frame()).unwrap();
+        let chart = vec![("0".to_string(), 5), ("1".to_string(), 10)];
+        terminal.frame(Frame { caption: "Chart".to_string(), image: Image::Chart(chart) }).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "Step 1\n -\n+ @\nChart\n0 |##### 5\n1 |########## 10\n");
+    }
+
+    #[test]
+    fn test_terminal_shrinks() {
+        let mut grid = Grid::new(20, 20, 0);
+        grid[Point::new(19, 0)] = 1;
+        let mut out = Vec::new();
+        let mut terminal = Terminal { columns: 10, delay: Duration::ZERO, clear: false, ..Terminal::new(&mut out) };
+        terminal.frame(Frame { caption: String::new(), image: Image::Grid(grid) }).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 11);
+        assert_eq!(text.lines().nth(1), Some("         @"));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut out = Vec::new();
+        Ppm::new(&mut out).frame(grid_frame()).unwrap();
+        let header = "P6\n399 266\n255\n";
+        assert!(out.starts_with(header.as_bytes()));
+        assert_eq!(out.len(), header.len() + 399 * 266 * 3);
+        // Top left cell is background, the bottom right one is the brightest
+        assert_eq!(out[header.len()..header.len() + 3], [0, 0, 0]);
+        assert_eq!(out[out.len() - 3..], [255, 230, 60]);
+    }
+
+    #[test]
+    fn test_chart_to_grid() {
+        let chart = Image::Chart(vec![("a".to_string(), 1), ("b".to_string(), 2)]);
+        let grid = chart.to_grid();
+        assert_eq!((grid.width(), grid.height()), (3, CHART_HEIGHT));
+        assert_eq!(grid.column(0).sum::<u32>(), CHART_HEIGHT as u32 / 2);
+        assert_eq!(grid.column(1).sum::<u32>(), 0);
+        assert_eq!(grid.column(2).sum::<u32>(), CHART_HEIGHT as u32);
+    }
+}
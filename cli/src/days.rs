@@ -0,0 +1,15 @@
+use aoc::Day;
+
+/// Every solved day, in order.
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::generated::<day01::Day01>().streaming::<day01::Day01>(),
+        Day::generated::<day02::Day02>().streaming::<day02::Day02>().visualized::<day02::Day02>(),
+        Day::generated::<day03::Day03>(),
+        Day::generated::<day04::Day04>().visualized::<day04::Day04>(),
+        Day::generated::<day05::Day05>().streaming::<day05::Day05>().visualized::<day05::Day05>(),
+        Day::generated::<day06::Day06>().visualized::<day06::Day06>(),
+        Day::generated::<day07::Day07>(),
+        Day::generated::<day08::Day08>(),
+    ]
+}
@@ -0,0 +1,455 @@
+mod days;
+mod scaffold;
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use aoc::client::Client;
+use aoc::verify::{self, Answers, Status};
+use aoc::report::{self, PartResult};
+use aoc::stream::StreamError;
+use aoc::vis::{Ppm, Sink, Terminal};
+use aoc::{bench, Day};
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one or more days' solutions
+    Run {
+        /// Day to run, or an inclusive range such as `1-5` (default: all days)
+        #[arg(short, long, value_parser = parse_days)]
+        day: Option<RangeInclusive<u32>>,
+        /// Only run this part
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        /// Input file, or `-` for stdin (default: dayNN/input.txt)
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+        /// Solve a line at a time instead of loading the whole input
+        #[arg(long)]
+        stream: bool,
+        /// Run every day at once and print a summary, carrying on past failures
+        #[arg(long, conflicts_with_all = ["day", "input", "stream"])]
+        all: bool,
+        /// Show the steps each solver takes to reach its answer
+        #[arg(long, conflicts_with_all = ["stream", "all"])]
+        explain: bool,
+    },
+    /// Time each day's parse, part 1 and part 2
+    Bench {
+        /// Day to benchmark, or an inclusive range such as `1-5` (default: all days)
+        #[arg(short, long, value_parser = parse_days)]
+        day: Option<RangeInclusive<u32>>,
+        /// Number of timed runs of each stage
+        #[arg(short = 'n', long, default_value_t = 100)]
+        iterations: usize,
+        /// Baseline file to compare against (default: bench_baseline.txt)
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Save this run's timings as the new baseline
+        #[arg(long)]
+        save: bool,
+    },
+    /// Check every day's answers on its real input against answers.toml
+    Verify {
+        /// Answers file (default: answers.toml)
+        #[arg(long)]
+        answers: Option<PathBuf>,
+    },
+    /// Download a day's input to dayNN/input.txt (uses AOC_SESSION)
+    Fetch {
+        #[arg(short, long, value_parser = puzzle_day())]
+        day: u32,
+    },
+    /// Submit an answer, computed by the solver unless given (uses AOC_SESSION)
+    Submit {
+        #[arg(short, long, value_parser = puzzle_day())]
+        day: u32,
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: u32,
+        /// Answer to submit (default: run the solver on the day's input)
+        #[arg(short, long)]
+        answer: Option<String>,
+    },
+    /// Print a random input for a day, for stress testing
+    Gen {
+        #[arg(short, long)]
+        day: u32,
+        /// Number of records (depths, commands, cards, ...) to generate
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+    /// Watch a day's simulation in the terminal, or save it as PPM frames
+    Vis {
+        #[arg(short, long)]
+        day: u32,
+        /// Input file, or `-` for stdin (default: dayNN/input.txt)
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        /// Pause between frames in the terminal, in milliseconds
+        #[arg(long, default_value_t = 50)]
+        delay: u64,
+        /// Write the frames to this PPM file instead of the terminal
+        #[arg(long)]
+        ppm: Option<PathBuf>,
+    },
+    /// Report on the shape of a day 1 sonar sweep: increases, runs, jumps, plateaus and stats
+    Sonar {
+        /// Input file, or `-` for stdin (default: day01/input.txt)
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Create a new day crate from the template and register it
+    New {
+        #[arg(short, long, value_parser = puzzle_day())]
+        day: u32,
+    },
+}
+
+// Days which have a puzzle, checked before anything talks to the site or touches files
+fn puzzle_day() -> clap::builder::RangedI64ValueParser<u32> {
+    clap::value_parser!(u32).range(1..=25)
+}
+
+fn parse_days(s: &str) -> Result<RangeInclusive<u32>, String> {
+    let parse = |n: &str| n.trim().parse::<u32>().map_err(|_| format!("Invalid day '{}'", n));
+    let range = match s.split_once('-') {
+        Some((from, to)) => parse(from)?..=parse(to)?,
+        None => parse(s)?..=parse(s)?,
+    };
+    if range.is_empty() {
+        return Err(format!("Empty day range '{}'", s));
+    }
+    Ok(range)
+}
+
+fn select_days(days: Option<RangeInclusive<u32>>) -> Result<Vec<Day>, String> {
+    let selected = days::all()
+        .into_iter()
+        .filter(|d| days.as_ref().is_none_or(|r| r.contains(&d.day)))
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        return Err("No solved days in the requested range".to_string());
+    }
+    Ok(selected)
+}
+
+fn run(days: Option<RangeInclusive<u32>>, part: Option<u32>, input: Option<PathBuf>, format: Format, stream: bool, explain: bool) -> Result<(), String> {
+    let selected = select_days(days)?;
+    if input.is_some() && selected.len() > 1 {
+        return Err("--input can only be used when running a single day".to_string());
+    }
+    if stream {
+        if let Some(day) = selected.iter().find(|d| !d.streams()) {
+            return Err(format!("Day {:02} doesn't support --stream", day.day));
+        }
+    }
+
+    let parts = match part {
+        Some(p) => p..=p,
+        None => 1..=2,
+    };
+    // Text output shows the steps under each answer, JSON output can't, so they go to stderr
+    if explain && format == Format::Json {
+        aoc::trace::enable(true);
+    }
+    // Text output stops at the first input error, JSON output reports them per part.
+    // Parts which fail on parsed input are reported in both
+    let mut results = Vec::new();
+    for day in selected {
+        let path = input.clone().unwrap_or_else(|| aoc::runner::input_path(day.day));
+        let name = if path.as_os_str() == "-" { "<stdin>".to_string() } else { path.display().to_string() };
+        if stream {
+            let day_results = match stream_day(&day, &path, parts.clone()) {
+                Ok(day_results) => day_results,
+                Err(e) if format == Format::Json => PartResult::failed(day.day, parts.clone(), &e.to_string()),
+                Err(StreamError::Parse(e)) => {
+                    eprintln!("error: {}\n --> {}:{}:{}", e.message, name, e.line, e.column);
+                    return Err(format!("Could not parse input for day {:02}", day.day));
+                }
+                Err(e) => return Err(format!("Failed to read {}: {}", name, e)),
+            };
+            print_results(format, day.day, &day_results, &[]);
+            results.extend(day_results);
+            continue;
+        }
+        let contents = match aoc::runner::read_input(&path) {
+            Ok(contents) => contents,
+            Err(e) if format == Format::Json => {
+                results.extend(PartResult::failed(day.day, parts.clone(), &format!("Failed to read {}: {}", path.display(), e)));
+                continue;
+            }
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let solved = if explain && format == Format::Text {
+            report::explain_parts(&day, &contents, parts.clone())
+        } else {
+            report::solve_parts(&day, &contents, parts.clone())
+                .map(|results| results.into_iter().map(|r| (r, Vec::new())).collect())
+        };
+        let (day_results, steps) = match solved {
+            Ok(solved) => solved.into_iter().unzip(),
+            Err(e) if format == Format::Json => (PartResult::failed(day.day, parts.clone(), &e.to_string()), Vec::new()),
+            Err(e) => {
+                eprint!("{}", e.render(&contents, &name));
+                return Err(format!("Could not parse input for day {:02}", day.day));
+            }
+        };
+        print_results(format, day.day, &day_results, &steps);
+        results.extend(day_results);
+    }
+
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&results).map_err(|e| e.to_string())?);
+    }
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        return Err(format!("{} part(s) failed", failed));
+    }
+    Ok(())
+}
+
+fn run_all(part: Option<u32>, format: Format) -> Result<(), String> {
+    let parts = match part {
+        Some(p) => p..=p,
+        None => 1..=2,
+    };
+    let read = |day| {
+        let path = aoc::runner::input_path(day);
+        aoc::runner::read_input(&path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+    let start = Instant::now();
+    let results = report::run_parallel(&days::all(), parts, read);
+    match format {
+        Format::Text => print!("{}", report::summary(&results, start.elapsed())),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&results).map_err(|e| e.to_string())?),
+    }
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    if failed > 0 {
+        return Err(format!("{} part(s) failed", failed));
+    }
+    Ok(())
+}
+
+fn stream_day(day: &Day, path: &Path, parts: RangeInclusive<u32>) -> Result<Vec<PartResult>, StreamError> {
+    let mut reader = aoc::runner::open_input(path)?;
+    report::stream_parts(day, &mut reader, parts).expect("day supports streaming")
+}
+
+// `steps` are the explained steps of each result, if any
+fn print_results(format: Format, day: u32, results: &[PartResult], steps: &[Vec<String>]) {
+    if format == Format::Text {
+        println!("Day {:02}", day);
+        for (i, r) in results.iter().enumerate() {
+            match (&r.answer, &r.error) {
+                (_, Some(e)) => println!("Part {} failed: {}", r.part, e),
+                (answer, None) => println!("Part {} = {}", r.part, answer.as_deref().unwrap_or_default()),
+            }
+            for step in steps.get(i).into_iter().flatten() {
+                println!("  {}", step);
+            }
+        }
+    }
+}
+
+fn bench(days: Option<RangeInclusive<u32>>, iterations: usize, baseline: Option<PathBuf>, save: bool) -> Result<(), String> {
+    if cfg!(debug_assertions) {
+        eprintln!("warning: benchmarking a debug build, use `cargo run --release`");
+    }
+    let mut measurements = Vec::new();
+    for day in select_days(days)? {
+        let path = aoc::runner::input_path(day.day);
+        let Ok(contents) = aoc::runner::read_input(&path) else {
+            eprintln!("warning: skipping day {:02}, no input at {}", day.day, path.display());
+            continue;
+        };
+        measurements.extend(bench::bench_day(&day, &contents, iterations)
+            .map_err(|e| format!("Could not parse input for day {:02}: {}", day.day, e))?);
+    }
+
+    let baseline_path = baseline.unwrap_or_else(|| aoc::runner::workspace_root().join("bench_baseline.txt"));
+    let previous = bench::load_baseline(&baseline_path).ok();
+    print!("{}", bench::report(&measurements, previous.as_ref()));
+    if save {
+        bench::save_baseline(&baseline_path, &measurements)
+            .map_err(|e| format!("Failed to write {}: {}", baseline_path.display(), e))?;
+        println!("Saved baseline to {}", baseline_path.display());
+    }
+    Ok(())
+}
+
+fn verify(answers: Option<PathBuf>) -> Result<(), String> {
+    let answers = Answers::load(&answers.unwrap_or_else(verify::answers_path))?;
+    let checks = verify::verify_all(&days::all(), &answers);
+    for check in checks.iter() {
+        println!("{}", check);
+    }
+    let failed = checks.iter().filter(|c| matches!(c.status, Status::Fail { .. })).count();
+    if failed > 0 {
+        return Err(format!("{} answer(s) did not match", failed));
+    }
+    Ok(())
+}
+
+fn fetch(day: u32) -> Result<(), String> {
+    let client = Client::from_env();
+    client.fetch_input(day).map_err(|e| e.to_string())?;
+    println!("Input for day {:02} saved to {}", day, client.input_path(day).display());
+    Ok(())
+}
+
+fn submit(day: u32, part: u32, answer: Option<String>) -> Result<(), String> {
+    let client = Client::from_env();
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let solution = select_days(Some(day..=day))?.remove(0);
+            let contents = client.fetch_input(day).map_err(|e| e.to_string())?;
+            let parsed = solution.parse(&contents)
+                .map_err(|e| format!("Could not parse input for day {:02}: {}", day, e))?;
+            solution.solve(part, &parsed)
+                .map_err(|e| format!("Day {:02} part {} failed: {}", day, part, e))?
+        }
+    };
+    println!("Day {:02} part {}: submitting {}", day, part, answer);
+    let outcome = client.submit(day, part, &answer).map_err(|e| e.to_string())?;
+    println!("{}", outcome);
+    Ok(())
+}
+
+fn generate(day: u32, size: usize, seed: u64) -> Result<(), String> {
+    let solution = select_days(Some(day..=day))?.remove(0);
+    let input = solution.generate(seed, size)
+        .ok_or_else(|| format!("Day {:02} has no input generator", day))?
+        .map_err(|e| format!("Day {:02}: {}", day, e))?;
+    print!("{}", input);
+    Ok(())
+}
+
+fn visualize(day: u32, input: Option<PathBuf>, delay: u64, ppm: Option<PathBuf>) -> Result<(), String> {
+    let solution = select_days(Some(day..=day))?.remove(0);
+    let path = input.unwrap_or_else(|| aoc::runner::input_path(day));
+    let contents = aoc::runner::read_input(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let parsed = solution.parse(&contents)
+        .map_err(|e| format!("Could not parse input for day {:02}: {}", day, e))?;
+    let mut sink: Box<dyn Sink> = match &ppm {
+        Some(ppm) => {
+            let file = File::create(ppm).map_err(|e| format!("Failed to create {}: {}", ppm.display(), e))?;
+            Box::new(Ppm::new(BufWriter::new(file)))
+        }
+        None => {
+            let mut terminal = Terminal::new(std::io::stdout());
+            terminal.delay = Duration::from_millis(delay);
+            Box::new(terminal)
+        }
+    };
+    solution.visualize(&parsed, sink.as_mut())
+        .ok_or_else(|| format!("Day {:02} has no visualization", day))?
+        .map_err(|e| format!("Failed to write frames: {}", e))?;
+    if let Some(ppm) = ppm {
+        println!("Frames saved to {}", ppm.display());
+    }
+    Ok(())
+}
+
+fn sonar(input: Option<PathBuf>, format: Format) -> Result<(), String> {
+    let path = input.unwrap_or_else(|| aoc::runner::input_path(1));
+    let contents = aoc::runner::read_input(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let depths = day01::parse_depths(&contents)
+        .map_err(|e| format!("Could not parse {}: {}", path.display(), e))?;
+    let profile = day01::DepthProfile::new(&depths);
+    match format {
+        Format::Text => print!("{}", profile),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&profile).map_err(|e| e.to_string())?),
+    }
+    Ok(())
+}
+
+fn new_day(day: u32) -> Result<(), String> {
+    scaffold::new_day(aoc::runner::workspace_root(), day)?;
+    println!("Created day{:02}", day);
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Run { part, format, all: true, .. } => run_all(part, format),
+        Command::Run { day, part, input, format, stream, all: false, explain } => run(day, part, input, format, stream, explain),
+        Command::Bench { day, iterations, baseline, save } => bench(day, iterations, baseline, save),
+        Command::Verify { answers } => verify(answers),
+        Command::Fetch { day } => fetch(day),
+        Command::Submit { day, part, answer } => submit(day, part, answer),
+        Command::Gen { day, size, seed } => generate(day, size, seed),
+        Command::Vis { day, input, delay, ppm } => visualize(day, input, delay, ppm),
+        Command::Sonar { input, format } => sonar(input, format),
+        Command::New { day } => new_day(day),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(parse_days("5"), Ok(5..=5));
+        assert_eq!(parse_days("1-7"), Ok(1..=7));
+        assert!(parse_days("7-1").is_err());
+        assert!(parse_days("five").is_err());
+    }
+
+    #[test]
+    fn test_day_range() {
+        for command in ["fetch", "submit", "new"] {
+            let args = |day| match command {
+                "submit" => vec!["aoc", command, "-d", day, "-p", "1"],
+                _ => vec!["aoc", command, "-d", day],
+            };
+            assert!(Cli::try_parse_from(args("0")).is_err(), "{} accepted day 0", command);
+            assert!(Cli::try_parse_from(args("26")).is_err(), "{} accepted day 26", command);
+            assert!(Cli::try_parse_from(args("25")).is_ok(), "{} rejected day 25", command);
+        }
+    }
+
+    #[test]
+    fn test_verify_answers() {
+        let answers = Answers::load(&verify::answers_path()).unwrap();
+        for check in verify::verify_all(&days::all(), &answers) {
+            assert!(!matches!(check.status, Status::Fail { .. }), "{}", check);
+        }
+    }
+}
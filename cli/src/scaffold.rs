@@ -0,0 +1,144 @@
+//! Generates a new day crate from the templates and registers it with the
+//! workspace, the runner and the answers file.
+
+use std::path::{Path, PathBuf};
+
+const CARGO_TEMPLATE: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_TEMPLATE: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_TEMPLATE: &str = include_str!("../templates/main.rs.tmpl");
+
+fn render(template: &str, day: u32) -> String {
+    template
+        .replace("{{NN}}", &format!("{:02}", day))
+        .replace("{{N}}", &day.to_string())
+}
+
+// Day number in a line such as `    "day05",`, `day05 = { path = "day05" }` or
+// `Day::of::<day05::Day05>(),`
+fn day_of_line(line: &str) -> Option<u32> {
+    let start = line.find("day")? + 3;
+    line.get(start..start + 2)?.parse().ok()
+}
+
+/// Inserts `new_line` among the lines mentioning a day matched by `is_entry`,
+/// keeping them ordered by day.
+fn insert_entry(contents: &str, is_entry: impl Fn(&str) -> bool, day: u32, new_line: &str) -> Result<String, String> {
+    let mut lines = contents.lines().map(|l| l.to_string()).collect::<Vec<String>>();
+    let entries = lines.iter()
+        .enumerate()
+        .filter(|(_, l)| is_entry(l))
+        .filter_map(|(i, l)| Some((i, day_of_line(l)?)))
+        .collect::<Vec<(usize, u32)>>();
+    if entries.iter().any(|(_, d)| *d == day) {
+        return Err(format!("Day {:02} is already registered", day));
+    }
+    let index = entries.iter()
+        .find(|(_, d)| *d > day)
+        .map(|(i, _)| *i)
+        .or_else(|| entries.last().map(|(i, _)| i + 1))
+        .ok_or_else(|| "Found no existing days to register the new day alongside".to_string())?;
+    lines.insert(index, new_line.to_string());
+    Ok(lines.join("\n") + "\n")
+}
+
+// New contents of the file at `path`, without writing them yet
+fn updated(path: PathBuf, f: impl FnOnce(&str) -> Result<String, String>) -> Result<(PathBuf, String), String> {
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let contents = f(&contents)?;
+    Ok((path, contents))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Creates `dayNN` under the workspace `root` and registers it.
+pub fn new_day(root: &Path, day: u32) -> Result<(), String> {
+    let name = format!("day{:02}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    // Work out every file's new contents before writing anything, so a file
+    // which can't be updated doesn't leave the day half registered
+    let updates = [
+        updated(root.join("Cargo.toml"), |c| {
+            let c = insert_entry(c, |l| l.trim().starts_with("\"day"), day, &format!("    \"{}\",", name))?;
+            insert_entry(&c, |l| l.contains("= { path = \"day"), day, &format!("{} = {{ path = \"{}\" }}", name, name))
+        })?,
+        updated(root.join("cli/Cargo.toml"), |c| insert_entry(c, |l| l.ends_with(".workspace = true"),
+            day, &format!("{}.workspace = true", name)))?,
+        updated(root.join("cli/src/days.rs"), |c| insert_entry(c, |l| l.contains("Day::"),
+            day, &format!("        Day::of::<{}::Day{:02}>(),", name, day)))?,
+        updated(root.join("answers.toml"), |c| {
+            let header = format!("[{}]", name);
+            if c.lines().any(|l| l.trim() == header) {
+                Ok(c.to_string())
+            } else {
+                Ok(format!("{}\n\n{}\n", c.trim_end(), header))
+            }
+        })?,
+    ];
+
+    std::fs::create_dir_all(dir.join("src"))
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for (file, template) in [("Cargo.toml", CARGO_TEMPLATE), ("src/lib.rs", LIB_TEMPLATE), ("src/main.rs", MAIN_TEMPLATE)] {
+        write(&dir.join(file), &render(template, day))?;
+    }
+    for (path, contents) in updates.iter() {
+        write(path, contents)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_entry() {
+        let members = "members = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n]\n";
+        let is_member = |l: &str| l.trim().starts_with("\"day");
+        assert_eq!(insert_entry(members, is_member, 2, "    \"day02\",").unwrap(),
+            "members = [\n    \"aoc\",\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n");
+        assert_eq!(insert_entry(members, is_member, 9, "    \"day09\",").unwrap(),
+            "members = [\n    \"aoc\",\n    \"day01\",\n    \"day03\",\n    \"day09\",\n]\n");
+        assert!(insert_entry(members, is_member, 3, "    \"day03\",").is_err());
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("cli/src")).unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\n    \"aoc\",\n    \"cli\",\n    \"day01\",\n]\n\n[workspace.dependencies]\naoc = { path = \"aoc\" }\nday01 = { path = \"day01\" }\nitertools = \"0.10.3\"\n").unwrap();
+        std::fs::write(root.join("cli/Cargo.toml"), "[dependencies]\naoc.workspace = true\nday01.workspace = true\n").unwrap();
+        std::fs::write(root.join("cli/src/days.rs"), "    vec![\n        Day::of::<day01::Day01>(),\n    ]\n").unwrap();
+        std::fs::write(root.join("answers.toml"), "[day01]\npart1 = 1\n").unwrap();
+
+        new_day(&root, 9).unwrap();
+        let read = |p: &str| std::fs::read_to_string(root.join(p)).unwrap();
+        assert!(read("Cargo.toml").contains("    \"day01\",\n    \"day09\",\n]"));
+        assert!(read("Cargo.toml").contains("day01 = { path = \"day01\" }\nday09 = { path = \"day09\" }\n"));
+        assert_eq!(read("cli/Cargo.toml"), "[dependencies]\naoc.workspace = true\nday01.workspace = true\nday09.workspace = true\n");
+        assert!(read("cli/src/days.rs").contains("        Day::of::<day09::Day09>(),\n    ]"));
+        assert_eq!(read("answers.toml"), "[day01]\npart1 = 1\n\n[day09]\n");
+        assert!(read("day09/src/lib.rs").contains("pub struct Day09;"));
+        assert!(read("day09/src/lib.rs").contains("const DAY: u32 = 9;"));
+        // Running the new day reports its parts as unsolved instead of panicking
+        assert!(read("day09/src/lib.rs").contains("Err(\"not solved yet\")"));
+        assert!(read("day09/Cargo.toml").contains("name = \"day09\""));
+
+        assert!(new_day(&root, 9).is_err());
+
+        // A file that can't be updated stops the day being created at all
+        std::fs::write(root.join("cli/src/days.rs"), "    vec![]\n").unwrap();
+        let before = read("Cargo.toml");
+        assert_eq!(new_day(&root, 10).unwrap_err(), "Found no existing days to register the new day alongside");
+        assert!(!root.join("day10").exists());
+        assert_eq!(read("Cargo.toml"), before);
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,231 @@
+//! Day 1: Sonar Sweep. Counting how often the sea floor depth increases.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::stream::{self, StreamError, Streaming};
+use aoc::{trace, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+pub mod monitor;
+pub mod profile;
+pub mod window;
+
+pub use monitor::{SonarMonitor, Trend};
+pub use profile::DepthProfile;
+
+/// Parses one depth per line.
+pub fn parse_depths(input: &str) -> Result<Vec<u32>, ParseError> {
+    Span::new(input).lines()
+        .map(|s| s.number())
+        .collect()
+}
+
+/// Number of values which are bigger than the one before, in a single pass.
+pub fn count_increases<T: PartialOrd>(depths: impl IntoIterator<Item = T>) -> usize {
+    let mut last = None;
+    let mut increases = 0;
+    for depth in depths {
+        if last.as_ref().is_some_and(|last| *last < depth) {
+            increases += 1;
+        }
+        last = Some(depth);
+    }
+    increases
+}
+
+/// Number of sliding windows of `size` depths whose sum is bigger than the one
+/// before. A size of 1 is the same as [`count_increases`]. Panics if `size` is 0.
+// Consecutive windows share all but their ends, so comparing their sums only
+// compares the ends, and nothing needs summing
+pub fn count_window_increases(depths: &[u32], size: usize) -> usize {
+    assert!(size > 0, "Window size must be at least 1");
+    depths.iter().zip(depths.iter().skip(size))
+        .filter(|(first, next)| first < next)
+        .inspect(|(first, next)| trace!("{} left the window, {} joined: increased", first, next))
+        .count()
+}
+
+/// Counts window increases for several window sizes at once, as in
+/// [`count_window_increases`], fed a depth at a time. Only the last depths
+/// for the biggest window are kept.
+#[derive(Clone, Debug)]
+pub struct WindowIncreases {
+    sizes: Vec<usize>,
+    longest: usize,
+    recent: VecDeque<u32>,
+    counts: Vec<usize>,
+}
+
+impl WindowIncreases {
+    /// Panics if any of `sizes` is 0.
+    pub fn new(sizes: &[usize]) -> Self {
+        assert!(sizes.iter().all(|size| *size > 0), "Window size must be at least 1");
+        let longest = sizes.iter().copied().max().unwrap_or(0);
+        WindowIncreases {
+            sizes: sizes.to_vec(),
+            longest,
+            recent: VecDeque::with_capacity(longest + 1),
+            counts: vec![0; sizes.len()],
+        }
+    }
+
+    pub fn push(&mut self, depth: u32) {
+        // The depth leaving a window of size n is the nth most recent one
+        for (size, count) in self.sizes.iter().zip(self.counts.iter_mut()) {
+            if self.recent.len() >= *size && self.recent[self.recent.len() - size] < depth {
+                *count += 1;
+            }
+        }
+        self.recent.push_back(depth);
+        if self.recent.len() > self.longest {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Increases so far, for each window size in the order they were given.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+}
+
+/// Increase counts for each of the window `sizes`, consuming `depths` once.
+pub fn count_increases_multi(depths: impl IntoIterator<Item = u32>, sizes: &[usize]) -> Vec<usize> {
+    let mut increases = WindowIncreases::new(sizes);
+    depths.into_iter().for_each(|depth| increases.push(depth));
+    increases.counts().to_vec()
+}
+
+/// Both parts in a single pass, remembering only the last three depths.
+pub fn count_increases_streaming(depths: impl IntoIterator<Item = u32>) -> (usize, usize) {
+    let counts = count_increases_multi(depths, &[1, 3]);
+    (counts[0], counts[1])
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<u32>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_depths(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        // Traced here, so count_increases doesn't need printable values
+        if aoc::trace::enabled() {
+            input.windows(2)
+                .filter(|w| w[0] < w[1])
+                .for_each(|w| trace!("{} -> {}: increased", w[0], w[1]));
+        }
+        count_increases(input.iter().cloned())
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        count_window_increases(input, 3)
+    }
+}
+
+impl Streaming for Day01 {
+    fn stream<R: BufRead>(reader: R) -> Result<(Self::Answer1, Self::Answer2), StreamError> {
+        let depths = stream::parse_lines(reader, |s| s.number());
+        stream::process_results(depths, |depths| count_increases_streaming(depths))
+    }
+}
+
+// Depths follow a random walk down from the surface, mostly increasing like a real sonar sweep
+impl Generator for Day01 {
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let mut depth = rng.random_range(100..200u32);
+        (0..size)
+            .map(|_| {
+                depth = depth.saturating_add_signed(rng.random_range(-20..=30));
+                format!("{}\n", depth)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test() {
+        let sample = "199
+            200
+            208
+            210
+            200
+            207
+            240
+            269
+            260
+            263";
+        let depths = parse_depths(sample).unwrap();
+        assert_eq!(7, count_increases(depths.iter().cloned()));
+        assert_eq!(5, count_window_increases(&depths, 3));
+        assert_eq!(7, count_window_increases(&depths, 1));
+        assert_eq!(0, count_window_increases(&depths, 10));
+        assert_eq!(Day01::stream(sample.as_bytes()).unwrap(), (7, 5));
+        let (_, steps) = aoc::trace::capture(|| Day01::part1(&depths[..3].to_vec()));
+        assert_eq!(steps, ["199 -> 200: increased", "200 -> 208: increased"]);
+
+        // Channels can only be read once
+        let (sender, receiver) = std::sync::mpsc::channel();
+        depths.iter().for_each(|d| sender.send(*d).unwrap());
+        drop(sender);
+        assert_eq!(count_increases_multi(receiver, &[1, 2, 3, 10]), [7, 5, 5, 0]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_depths("199\n2OO\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "2OO"));
+    }
+
+    // Found by fuzzing
+    #[test]
+    fn test_regressions() {
+        // Window sums overflowed a u32
+        let depths = parse_depths("4294967295\n4294967295\n4294967295\n4294967295\n").unwrap();
+        assert_eq!(count_window_increases(&depths, 3), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_window_increases(depths in prop::collection::vec(any::<u32>(), 0..200), size in 1..10usize) {
+            let sums = depths.windows(size).map(|w| w.iter().map(|d| *d as u64).sum::<u64>());
+            prop_assert_eq!(count_window_increases(&depths, size), count_increases(sums));
+
+            let increases = count_increases(depths.iter().cloned());
+            prop_assert_eq!(count_window_increases(&depths, 1), increases);
+            let window_increases = count_window_increases(&depths, 3);
+            prop_assert_eq!(count_increases_streaming(depths.iter().cloned()), (increases, window_increases));
+
+            // Each window size counts the same as on its own
+            let sizes = [size, 1, size + 3, size];
+            let expected = sizes.iter().map(|n| count_window_increases(&depths, *n)).collect::<Vec<usize>>();
+            prop_assert_eq!(count_increases_multi(depths, &sizes), expected);
+        }
+
+        #[test]
+        fn prop_parse(depths in prop::collection::vec(any::<u32>(), 0..50)) {
+            let text = depths.iter().map(|d| format!("{}\n", d)).collect::<String>();
+            prop_assert_eq!(parse_depths(&text).unwrap(), depths);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day01>();
+    }
+}
\ No newline at end of file
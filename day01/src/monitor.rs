@@ -0,0 +1,157 @@
+//! Watching a live feed of depth readings, one at a time.
+
+use std::collections::VecDeque;
+
+use crate::WindowIncreases;
+
+/// Which way the sea floor is heading, from the last two window sums.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trend {
+    Deeper,
+    Shallower,
+    Level,
+}
+
+/// Running statistics over depth readings, updated as each one arrives.
+/// Memory use only depends on the window size.
+#[derive(Clone, Debug)]
+pub struct SonarMonitor {
+    window: usize,
+    increases: WindowIncreases,
+    // The last window of depths and the one before it
+    recent: VecDeque<u32>,
+    recent_sum: u64,
+    readings: usize,
+    streak: usize,
+    longest_streak: usize,
+}
+
+impl Default for SonarMonitor {
+    /// Monitor with the puzzle's window of three readings.
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl SonarMonitor {
+    /// Monitor comparing windows of `window` readings. Panics if `window` is 0.
+    pub fn new(window: usize) -> Self {
+        SonarMonitor {
+            window,
+            increases: WindowIncreases::new(&[1, window]),
+            recent: VecDeque::with_capacity(window + 1),
+            recent_sum: 0,
+            readings: 0,
+            streak: 0,
+            longest_streak: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: u32) {
+        if let Some(last) = self.recent.back() {
+            self.streak = if *last < depth { self.streak + 1 } else { 0 };
+            self.longest_streak = self.longest_streak.max(self.streak);
+        }
+        self.increases.push(depth);
+        self.recent.push_back(depth);
+        self.recent_sum += depth as u64;
+        if self.recent.len() > self.window + 1 {
+            self.recent_sum -= self.recent.pop_front().unwrap() as u64;
+        }
+        self.readings += 1;
+    }
+
+    pub fn window_size(&self) -> usize {
+        self.window
+    }
+
+    pub fn readings(&self) -> usize {
+        self.readings
+    }
+
+    /// Number of readings deeper than the one before, as in [`count_increases`](crate::count_increases).
+    pub fn increases(&self) -> usize {
+        self.increases.counts()[0]
+    }
+
+    /// Number of window sums bigger than the one before, as in
+    /// [`count_window_increases`](crate::count_window_increases).
+    pub fn window_increases(&self) -> usize {
+        self.increases.counts()[1]
+    }
+
+    /// Number of increases in a row up to the latest reading.
+    pub fn streak(&self) -> usize {
+        self.streak
+    }
+
+    pub fn longest_streak(&self) -> usize {
+        self.longest_streak
+    }
+
+    /// Sum of the last window of readings, once there are enough of them.
+    pub fn window_sum(&self) -> Option<u64> {
+        match self.recent.len() {
+            n if n == self.window + 1 => Some(self.recent_sum - *self.recent.front().unwrap() as u64),
+            n if n == self.window => Some(self.recent_sum),
+            _ => None,
+        }
+    }
+
+    /// Sum of the window before the last one.
+    pub fn previous_window_sum(&self) -> Option<u64> {
+        (self.recent.len() == self.window + 1).then(|| self.recent_sum - *self.recent.back().unwrap() as u64)
+    }
+
+    /// How the last window sum compares to the one before, once there are two.
+    pub fn trend(&self) -> Option<Trend> {
+        let (previous, last) = (self.previous_window_sum()?, self.window_sum()?);
+        Some(match last.cmp(&previous) {
+            std::cmp::Ordering::Greater => Trend::Deeper,
+            std::cmp::Ordering::Less => Trend::Shallower,
+            std::cmp::Ordering::Equal => Trend::Level,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_monitor() {
+        let mut monitor = SonarMonitor::default();
+        let mut trends = Vec::new();
+        for depth in [199, 200, 208, 210, 200, 207, 240, 269, 260, 263] {
+            monitor.push(depth);
+            trends.push(monitor.trend());
+        }
+        assert_eq!((monitor.readings(), monitor.increases(), monitor.window_increases()), (10, 7, 5));
+        assert_eq!((monitor.streak(), monitor.longest_streak()), (1, 3));
+        assert_eq!((monitor.previous_window_sum(), monitor.window_sum()), (Some(769), Some(792)));
+        use Trend::*;
+        assert_eq!(trends, [None, None, None, Some(Deeper), Some(Level), Some(Shallower),
+            Some(Deeper), Some(Deeper), Some(Deeper), Some(Deeper)]);
+
+        let mut monitor = SonarMonitor::new(2);
+        monitor.push(5);
+        assert_eq!((monitor.window_sum(), monitor.trend()), (None, None));
+        monitor.push(6);
+        assert_eq!((monitor.window_sum(), monitor.trend()), (Some(11), None));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_batch(depths in prop::collection::vec(any::<u32>(), 0..100), window in 1..8usize) {
+            let mut monitor = SonarMonitor::new(window);
+            depths.iter().for_each(|d| monitor.push(*d));
+            prop_assert_eq!(monitor.increases(), crate::count_increases(depths.iter()));
+            prop_assert_eq!(monitor.window_increases(), crate::count_window_increases(&depths, window));
+
+            let sums = depths.windows(window).map(|w| w.iter().map(|d| *d as u64).sum::<u64>()).collect::<Vec<u64>>();
+            prop_assert_eq!(monitor.window_sum(), sums.last().cloned());
+            prop_assert_eq!(monitor.previous_window_sum(), sums.len().checked_sub(2).map(|i| sums[i]));
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Analysis of the shape of a sonar sweep, beyond counting increases.
+
+use std::fmt;
+
+use serde::Serialize;
+
+/// Consecutive readings, by their index into the depths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Run {
+    pub start: usize,
+    pub len: usize,
+}
+
+/// Change in depth between a reading and the one before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Jump {
+    /// Index of the reading after the jump.
+    pub position: usize,
+    pub from: u32,
+    pub to: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Stats {
+    pub min: u32,
+    pub max: u32,
+    pub mean: f64,
+    /// Population standard deviation.
+    pub stddev: f64,
+}
+
+/// Report on a sonar sweep. Positions are indices into the depths.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DepthProfile {
+    pub readings: usize,
+    /// Readings deeper than the one before.
+    pub increases: Vec<usize>,
+    /// Readings shallower than the one before.
+    pub decreases: Vec<usize>,
+    /// Longest run of readings each deeper than the last, the first if there's a tie.
+    pub longest_increasing: Option<Run>,
+    pub longest_decreasing: Option<Run>,
+    /// Biggest change either way, the first if there's a tie.
+    pub largest_jump: Option<Jump>,
+    /// Runs of two or more equal readings.
+    pub plateaus: Vec<Run>,
+    /// None if there are no readings.
+    pub stats: Option<Stats>,
+}
+
+// Splits the depths into maximal runs where each pair of neighbours matches `pair`
+fn runs<'a>(depths: &'a [u32], pair: impl Fn(u32, u32) -> bool + 'a) -> impl Iterator<Item = Run> + 'a {
+    let mut start = 0;
+    (1..=depths.len()).filter_map(move |i| {
+        if i < depths.len() && pair(depths[i - 1], depths[i]) {
+            return None;
+        }
+        let run = Run { start, len: i - start };
+        start = i;
+        (run.len > 1).then_some(run)
+    })
+}
+
+fn longest(runs: impl Iterator<Item = Run>) -> Option<Run> {
+    runs.fold(None, |longest: Option<Run>, run| match longest {
+        Some(longest) if longest.len >= run.len => Some(longest),
+        _ => Some(run),
+    })
+}
+
+impl DepthProfile {
+    pub fn new(depths: &[u32]) -> Self {
+        let changes = || depths.windows(2).enumerate().map(|(i, w)| (i + 1, w[0], w[1]));
+        let stats = (!depths.is_empty()).then(|| {
+            let mean = depths.iter().map(|d| *d as f64).sum::<f64>() / depths.len() as f64;
+            let variance = depths.iter().map(|d| (*d as f64 - mean).powi(2)).sum::<f64>() / depths.len() as f64;
+            Stats {
+                min: *depths.iter().min().unwrap(),
+                max: *depths.iter().max().unwrap(),
+                mean,
+                stddev: variance.sqrt(),
+            }
+        });
+        DepthProfile {
+            readings: depths.len(),
+            increases: changes().filter(|(_, from, to)| from < to).map(|(i, _, _)| i).collect(),
+            decreases: changes().filter(|(_, from, to)| from > to).map(|(i, _, _)| i).collect(),
+            longest_increasing: longest(runs(depths, |a, b| a < b)),
+            longest_decreasing: longest(runs(depths, |a, b| a > b)),
+            largest_jump: changes()
+                .filter(|(_, from, to)| from != to)
+                .fold(None, |largest: Option<Jump>, (position, from, to)| match largest {
+                    Some(largest) if largest.from.abs_diff(largest.to) >= from.abs_diff(to) => Some(largest),
+                    _ => Some(Jump { position, from, to }),
+                }),
+            plateaus: runs(depths, |a, b| a == b).collect(),
+            stats,
+        }
+    }
+}
+
+fn positions(positions: &[usize]) -> String {
+    if positions.is_empty() {
+        return "none".to_string();
+    }
+    let list = positions.iter().map(|p| p.to_string()).collect::<Vec<String>>();
+    format!("{} at {}", positions.len(), list.join(", "))
+}
+
+impl fmt::Display for Run {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} readings from {}", self.len, self.start)
+    }
+}
+
+impl fmt::Display for DepthProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Readings: {}", self.readings)?;
+        if let Some(stats) = &self.stats {
+            writeln!(f, "Depth: min {}, max {}, mean {:.1}, stddev {:.1}", stats.min, stats.max, stats.mean, stats.stddev)?;
+        }
+        writeln!(f, "Increases: {}", positions(&self.increases))?;
+        writeln!(f, "Decreases: {}", positions(&self.decreases))?;
+        if let Some(run) = &self.longest_increasing {
+            writeln!(f, "Longest increasing run: {}", run)?;
+        }
+        if let Some(run) = &self.longest_decreasing {
+            writeln!(f, "Longest decreasing run: {}", run)?;
+        }
+        if let Some(jump) = &self.largest_jump {
+            writeln!(f, "Largest jump: {} -> {} at {}", jump.from, jump.to, jump.position)?;
+        }
+        let plateaus = self.plateaus.iter().map(|run| run.to_string()).collect::<Vec<String>>();
+        writeln!(f, "Plateaus: {}", if plateaus.is_empty() { "none".to_string() } else { plateaus.join(", ") })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile() {
+        let profile = DepthProfile::new(&[199, 200, 208, 210, 200, 207, 240, 269, 260, 263]);
+        assert_eq!(profile.increases, [1, 2, 3, 5, 6, 7, 9]);
+        assert_eq!(profile.decreases, [4, 8]);
+        assert_eq!(profile.longest_increasing, Some(Run { start: 0, len: 4 }));
+        assert_eq!(profile.longest_decreasing, Some(Run { start: 3, len: 2 }));
+        assert_eq!(profile.largest_jump, Some(Jump { position: 6, from: 207, to: 240 }));
+        assert!(profile.plateaus.is_empty());
+        let stats = profile.stats.unwrap();
+        assert_eq!((stats.min, stats.max, stats.mean), (199, 269, 225.6));
+        assert!((stats.stddev - 27.551).abs() < 0.001);
+
+        assert_eq!(profile.to_string(), "Readings: 10
+Depth: min 199, max 269, mean 225.6, stddev 27.6
+Increases: 7 at 1, 2, 3, 5, 6, 7, 9
+Decreases: 2 at 4, 8
+Longest increasing run: 4 readings from 0
+Longest decreasing run: 2 readings from 3
+Largest jump: 207 -> 240 at 6
+Plateaus: none
+");
+    }
+
+    #[test]
+    fn test_plateaus() {
+        let profile = DepthProfile::new(&[5, 5, 5, 4, 4, 9, 1, 1]);
+        assert_eq!(profile.plateaus, [Run { start: 0, len: 3 }, Run { start: 3, len: 2 }, Run { start: 6, len: 2 }]);
+        assert_eq!(profile.largest_jump, Some(Jump { position: 6, from: 9, to: 1 }));
+        assert_eq!(profile.longest_increasing, Some(Run { start: 4, len: 2 }));
+
+        let empty = DepthProfile::new(&[]);
+        assert_eq!((empty.stats, empty.largest_jump, empty.longest_increasing), (None, None, None));
+        assert_eq!(serde_json::to_string(&empty).unwrap(),
+            r#"{"readings":0,"increases":[],"decreases":[],"longest_increasing":null,"longest_decreasing":null,"largest_jump":null,"plateaus":[],"stats":null}"#);
+    }
+}
@@ -0,0 +1,283 @@
+//! Sliding windows over depths, compared by a pluggable aggregate such as
+//! their sum, mean, median, min or max.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Summarises a sliding window, updated as depths join and leave it.
+pub trait Aggregator {
+    type Output: PartialOrd;
+
+    /// Called when `depth` joins the window.
+    fn push(&mut self, _depth: u32) {}
+
+    /// Called when `depth`, the oldest one in the window, leaves it.
+    fn pop(&mut self, _depth: u32) {}
+
+    /// Aggregate of the full `window`.
+    fn get(&mut self, window: &VecDeque<u32>) -> Self::Output;
+}
+
+/// Any closure over the window is an aggregator, which looks at the whole
+/// window every time.
+impl<F, T> Aggregator for F
+    where F: FnMut(&VecDeque<u32>) -> T, T: PartialOrd
+{
+    type Output = T;
+
+    fn get(&mut self, window: &VecDeque<u32>) -> T {
+        self(window)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Sum(u64);
+
+impl Aggregator for Sum {
+    type Output = u64;
+
+    fn push(&mut self, depth: u32) {
+        self.0 += depth as u64;
+    }
+
+    fn pop(&mut self, depth: u32) {
+        self.0 -= depth as u64;
+    }
+
+    fn get(&mut self, _: &VecDeque<u32>) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Mean(Sum);
+
+impl Aggregator for Mean {
+    type Output = f64;
+
+    fn push(&mut self, depth: u32) {
+        self.0.push(depth);
+    }
+
+    fn pop(&mut self, depth: u32) {
+        self.0.pop(depth);
+    }
+
+    fn get(&mut self, window: &VecDeque<u32>) -> f64 {
+        self.0.get(window) as f64 / window.len() as f64
+    }
+}
+
+/// Smallest (or with `MAX`, biggest) depth in the window. Keeps a deque of
+/// the depths which could still become the extreme one, so each depth is
+/// pushed and popped at most once.
+#[derive(Clone, Debug, Default)]
+pub struct Extreme<const MAX: bool> {
+    // (index, depth), with depths getting less extreme from front to back
+    candidates: VecDeque<(usize, u32)>,
+    pushed: usize,
+    popped: usize,
+}
+
+pub type Min = Extreme<false>;
+pub type Max = Extreme<true>;
+
+impl<const MAX: bool> Aggregator for Extreme<MAX> {
+    type Output = u32;
+
+    fn push(&mut self, depth: u32) {
+        // Anything less extreme than the new depth leaves the window before it does
+        while self.candidates.back().is_some_and(|(_, d)| if MAX { *d <= depth } else { *d >= depth }) {
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back((self.pushed, depth));
+        self.pushed += 1;
+    }
+
+    fn pop(&mut self, _: u32) {
+        if self.candidates.front().is_some_and(|(i, _)| *i == self.popped) {
+            self.candidates.pop_front();
+        }
+        self.popped += 1;
+    }
+
+    fn get(&mut self, _: &VecDeque<u32>) -> u32 {
+        self.candidates.front().expect("window is empty").1
+    }
+}
+
+/// Middle depth of the window, or the mean of the middle two. The lower half
+/// of the window is kept in a max-heap and the upper half in a min-heap.
+/// Depths leaving the window are only removed once they reach the top of
+/// their heap.
+#[derive(Clone, Debug, Default)]
+pub struct Median {
+    low: BinaryHeap<u32>,
+    high: BinaryHeap<Reverse<u32>>,
+    // Depths which have left the window but are still in a heap
+    removed: HashMap<u32, usize>,
+    // Number of depths in each half which are still in the window
+    low_len: usize,
+    high_len: usize,
+}
+
+impl Median {
+    fn prune(&mut self) {
+        while let Some(&top) = self.low.peek() {
+            if !self.take_removed(top) {
+                break;
+            }
+            self.low.pop();
+        }
+        while let Some(&Reverse(top)) = self.high.peek() {
+            if !self.take_removed(top) {
+                break;
+            }
+            self.high.pop();
+        }
+    }
+
+    fn take_removed(&mut self, depth: u32) -> bool {
+        match self.removed.get_mut(&depth) {
+            Some(count) => {
+                *count -= 1;
+                if *count == 0 {
+                    self.removed.remove(&depth);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Keeps the lower half the same size as the upper half, or one bigger
+    fn rebalance(&mut self) {
+        if self.low_len > self.high_len + 1 {
+            let top = self.low.pop().unwrap();
+            self.high.push(Reverse(top));
+            self.low_len -= 1;
+            self.high_len += 1;
+        } else if self.low_len < self.high_len {
+            let Reverse(top) = self.high.pop().unwrap();
+            self.low.push(top);
+            self.high_len -= 1;
+            self.low_len += 1;
+        }
+        self.prune();
+    }
+}
+
+impl Aggregator for Median {
+    type Output = f64;
+
+    fn push(&mut self, depth: u32) {
+        if self.low.peek().is_none_or(|top| depth <= *top) {
+            self.low.push(depth);
+            self.low_len += 1;
+        } else {
+            self.high.push(Reverse(depth));
+            self.high_len += 1;
+        }
+        self.rebalance();
+    }
+
+    fn pop(&mut self, depth: u32) {
+        *self.removed.entry(depth).or_default() += 1;
+        // Everything in the upper half is at least the top of the lower half
+        if self.low.peek().is_some_and(|top| depth <= *top) {
+            self.low_len -= 1;
+        } else {
+            self.high_len -= 1;
+        }
+        self.prune();
+        self.rebalance();
+    }
+
+    fn get(&mut self, _: &VecDeque<u32>) -> f64 {
+        let low = *self.low.peek().expect("window is empty") as f64;
+        if self.low_len > self.high_len {
+            low
+        } else {
+            (low + self.high.peek().unwrap().0 as f64) / 2.0
+        }
+    }
+}
+
+/// Aggregate of every full window of `size` depths, in order. Panics if `size` is 0.
+pub fn sliding<A: Aggregator>(depths: impl IntoIterator<Item = u32>, size: usize, mut aggregator: A) -> impl Iterator<Item = A::Output> {
+    assert!(size > 0, "Window size must be at least 1");
+    let mut window = VecDeque::with_capacity(size + 1);
+    depths.into_iter().filter_map(move |depth| {
+        window.push_back(depth);
+        aggregator.push(depth);
+        if window.len() > size {
+            aggregator.pop(window.pop_front().unwrap());
+        }
+        (window.len() == size).then(|| aggregator.get(&window))
+    })
+}
+
+/// Number of windows of `size` depths whose aggregate is bigger than the one before.
+pub fn count_aggregate_increases<A: Aggregator>(depths: &[u32], size: usize, aggregator: A) -> usize {
+    let mut last = None;
+    let mut increases = 0;
+    for value in sliding(depths.iter().cloned(), size, aggregator) {
+        if last.as_ref().is_some_and(|last| *last < value) {
+            increases += 1;
+        }
+        last = Some(value);
+    }
+    increases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SAMPLE: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_aggregators() {
+        assert_eq!(sliding(SAMPLE, 3, Sum::default()).collect::<Vec<_>>(), [607, 618, 618, 617, 647, 716, 769, 792]);
+        assert_eq!(sliding(SAMPLE, 4, Mean::default()).next(), Some(204.25));
+        assert_eq!(sliding(SAMPLE, 3, Min::default()).collect::<Vec<_>>(), [199, 200, 200, 200, 200, 207, 240, 260]);
+        assert_eq!(sliding(SAMPLE, 3, Max::default()).collect::<Vec<_>>(), [208, 210, 210, 210, 240, 269, 269, 269]);
+        assert_eq!(sliding(SAMPLE, 3, Median::default()).collect::<Vec<_>>(), [200.0, 208.0, 208.0, 207.0, 207.0, 240.0, 260.0, 263.0]);
+        assert_eq!(sliding(SAMPLE, 2, Median::default()).nth(1), Some(204.0));
+        let spread = |w: &VecDeque<u32>| w.iter().max().unwrap() - w.iter().min().unwrap();
+        assert_eq!(sliding(SAMPLE, 3, spread).collect::<Vec<_>>(), [9, 10, 10, 10, 40, 62, 29, 9]);
+
+        assert_eq!(count_aggregate_increases(&SAMPLE, 3, Sum::default()), 5);
+        assert_eq!(count_aggregate_increases(&SAMPLE, 1, Max::default()), 7);
+        assert_eq!(count_aggregate_increases(&SAMPLE, 3, Median::default()), 4);
+        assert_eq!(count_aggregate_increases(&SAMPLE, 11, Sum::default()), 0);
+    }
+
+    fn median(window: &VecDeque<u32>) -> f64 {
+        let mut sorted = window.iter().cloned().collect::<Vec<u32>>();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 1 {
+            sorted[mid] as f64
+        } else {
+            (sorted[mid - 1] as f64 + sorted[mid] as f64) / 2.0
+        }
+    }
+
+    proptest! {
+        // Small depths, so windows are full of duplicates
+        #[test]
+        fn prop_matches_naive(depths in prop::collection::vec(0..20u32, 0..100), size in 1..12usize) {
+            let naive = |f: fn(&VecDeque<u32>) -> f64| sliding(depths.iter().cloned(), size, f).collect::<Vec<f64>>();
+            prop_assert_eq!(sliding(depths.iter().cloned(), size, Median::default()).collect::<Vec<f64>>(), naive(median));
+            prop_assert_eq!(sliding(depths.iter().cloned(), size, Mean::default()).collect::<Vec<f64>>(),
+                naive(|w| w.iter().map(|d| *d as f64).sum::<f64>() / w.len() as f64));
+            prop_assert_eq!(sliding(depths.iter().cloned(), size, Min::default()).collect::<Vec<u32>>(),
+                sliding(depths.iter().cloned(), size, |w: &VecDeque<u32>| *w.iter().min().unwrap()).collect::<Vec<u32>>());
+            prop_assert_eq!(sliding(depths.iter().cloned(), size, Max::default()).collect::<Vec<u32>>(),
+                sliding(depths.iter().cloned(), size, |w: &VecDeque<u32>| *w.iter().max().unwrap()).collect::<Vec<u32>>());
+            prop_assert_eq!(count_aggregate_increases(&depths, size, Sum::default()), crate::count_window_increases(&depths, size));
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use aoc::Solution;
+use day01::*;
+
+const SAMPLE: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+#[test]
+fn test_public_api() {
+    let depths = parse_depths(SAMPLE).unwrap();
+    assert_eq!(count_increases(depths.iter()), 7);
+    assert_eq!(count_window_increases(&depths, 3), 5);
+    assert_eq!(count_increases_streaming(depths.into_iter()), (7, 5));
+    assert_eq!(Day01::part1(&Day01::parse(SAMPLE).unwrap()), 7);
+}
+
+#[test]
+fn test_profile() {
+    let profile = DepthProfile::new(&parse_depths(SAMPLE).unwrap());
+    assert_eq!(profile.increases.len(), count_increases(parse_depths(SAMPLE).unwrap()));
+    assert_eq!(profile.largest_jump.map(|jump| (jump.from, jump.to)), Some((207, 240)));
+}
@@ -0,0 +1,266 @@
+//! Day 2: Dive! Steering the submarine with a list of commands.
+
+use std::io::{self, BufRead};
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::stream::{self, StreamError, Streaming};
+use aoc::vis::{Frame, Image, Sink, Visualize};
+use aoc::{trace, Grid, ParseError, Point, Solution, Vec2};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Parses a command such as `forward 5` into the move it makes in part 1,
+/// where y is depth, so `down` is positive.
+pub fn instruction_to_vec(line: Span) -> Result<Vec2, ParseError> {
+    let mut split = line.fields(' ');
+    let direction = split.next().ok_or_else(|| line.at_end().error("Missing direction field"))?;
+    let distance = split.next().ok_or_else(|| line.at_end().error("Missing distance field"))?;
+    let distance = distance.number::<i32>()
+        .map_err(|_| distance.error(format!("Invalid distance '{}' (must be integer)", distance.as_str())))?;
+    match direction.as_str() {
+        "forward" => Ok(Vec2::new(distance, 0)),
+        "down" => Ok(Vec2::new(0, distance)),
+        "up" => Ok(Vec2::new(0, -distance)),
+        s => Err(direction.error(format!("Unexpected direction '{}'", s)))
+    }
+}
+
+/// Keeps a running bound on the answers as commands are parsed, so running a
+/// program which passed it can't overflow. With distances under 10, like the
+/// real input, it takes hundreds of billions of commands to fail.
+// |x| is at most the total forward distance F and |aim| the total down and up
+// distance A, so |depth| <= A * F and both answers are at most F * F * A
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Bound {
+    forward: i128,
+    vertical: i128,
+}
+
+impl Bound {
+    /// Adds `instruction`, parsed from `line`, failing if the answers could
+    /// then overflow an i128.
+    pub fn add(&mut self, line: Span, instruction: Vec2) -> Result<(), ParseError> {
+        self.forward += instruction.x.unsigned_abs() as i128;
+        self.vertical += instruction.y.unsigned_abs() as i128;
+        self.forward.checked_mul(self.forward)
+            .and_then(|f| f.checked_mul(self.vertical))
+            .map(|_| ())
+            .ok_or_else(|| line.error("Program is too long, its answers could overflow an i128"))
+    }
+}
+
+/// Parses one command per line.
+pub fn parse_program(program_str: &str) -> Result<Vec<Vec2>, ParseError> {
+    let mut bound = Bound::default();
+    Span::new(program_str).lines()
+        .map(|line| {
+            let instruction = instruction_to_vec(line)?;
+            bound.add(line, instruction)?;
+            Ok(instruction)
+        })
+        .collect()
+}
+
+/// Runs the program for both parts at once, yielding (aim, x, depth) after
+/// each instruction. In part 1 down and up move the submarine, in part 2 they
+/// adjust its aim, which is the same as its part 1 depth.
+/// Overflows unless the instructions passed a [`Bound`].
+pub fn trace_program(instructions: impl Iterator<Item = Vec2>) -> impl Iterator<Item = (i128, i128, i128)> {
+    instructions.scan((0i128, 0i128, 0i128), |(aim, x, depth), instruction| {
+        if instruction.y == 0 {
+            // Forward
+            *x += instruction.x as i128;
+            *depth += *aim * instruction.x as i128;
+        } else {
+            // Aim adjust
+            *aim += instruction.y as i128;
+        }
+        trace!("Move {}: x = {}, aim (part 1 depth) = {}, depth = {}", instruction, x, aim, depth);
+        Some((*aim, *x, *depth))
+    })
+}
+
+/// Final x times depth, for parts 1 and 2.
+pub fn run_program(instructions: impl Iterator<Item = Vec2>) -> (i128, i128) {
+    let (aim, x, depth) = trace_program(instructions).last().unwrap_or_default();
+    (x * aim, x * depth)
+}
+
+fn run_program_p1(instructions: &[Vec2]) -> i128 {
+    run_program(instructions.iter().cloned()).0
+}
+
+fn run_program_p2(instructions: &[Vec2]) -> i128 {
+    run_program(instructions.iter().cloned()).1
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Vec2>;
+    type Answer1 = i128;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_program(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        run_program_p1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        run_program_p2(input)
+    }
+}
+
+impl Streaming for Day02 {
+    fn stream<R: BufRead>(reader: R) -> Result<(Self::Answer1, Self::Answer2), StreamError> {
+        let mut bound = Bound::default();
+        let instructions = stream::parse_lines(reader, |line| {
+            let instruction = instruction_to_vec(line)?;
+            bound.add(line, instruction)?;
+            Ok(instruction)
+        });
+        stream::process_results(instructions, |instructions| run_program(instructions))
+    }
+}
+
+// Size of the canvas the part 2 path is scaled onto
+const CANVAS_WIDTH: i128 = 100;
+const CANVAS_HEIGHT: i128 = 50;
+const FRAMES: usize = 100;
+
+impl Visualize for Day02 {
+    fn visualize(input: &Self::Input, sink: &mut dyn Sink) -> io::Result<()> {
+        let path = trace_program(input.iter().cloned())
+            .map(|(_, x, depth)| (x, depth))
+            .collect::<Vec<(i128, i128)>>();
+        let max_x = path.iter().map(|p| p.0).max().unwrap_or(0).max(1);
+        let min_depth = path.iter().map(|p| p.1).min().unwrap_or(0).min(0);
+        let max_depth = path.iter().map(|p| p.1).max().unwrap_or(0).max(min_depth + 1);
+        let to_canvas = |(x, depth): (i128, i128)| Point::new(
+            (x * (CANVAS_WIDTH - 1) / max_x) as i32,
+            ((depth - min_depth) * (CANVAS_HEIGHT - 1) / (max_depth - min_depth)) as i32);
+
+        let mut canvas = Grid::new(CANVAS_WIDTH as usize, CANVAS_HEIGHT as usize, 0);
+        let mut last = to_canvas((0, 0));
+        let every = path.len().div_ceil(FRAMES).max(1);
+        for (step, &(x, depth)) in path.iter().enumerate() {
+            let pos = to_canvas((x, depth));
+            for p in last.line_to(pos) {
+                canvas[p] = 1;
+            }
+            last = pos;
+            if (step + 1) % every == 0 || step + 1 == path.len() {
+                let mut image = canvas.clone();
+                image[pos] = 2;
+                let caption = format!("Step {}/{}: x = {}, depth = {}", step + 1, path.len(), x, depth);
+                sink.frame(Frame { caption, image: Image::Grid(image) })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Generator for Day02 {
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let direction = match rng.random_range(0..6) {
+                    0..=2 => "forward",
+                    3 | 4 => "down",
+                    _ => "up",
+                };
+                format!("{} {}\n", direction, rng.random_range(1..10))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    
+    #[test]
+    fn test_instruction_parse() {
+        assert_eq!(Ok(Vec2::new(2, 0)), instruction_to_vec(Span::new("forward 2")));
+        assert_eq!(Ok(Vec2::new(0, 2)), instruction_to_vec(Span::new("down 2")));
+        assert_eq!(Ok(Vec2::new(0, -3)), instruction_to_vec(Span::new("up 3")));
+        assert_eq!("Unexpected direction 'left'", instruction_to_vec(Span::new("left 5")).unwrap_err().message);
+        assert_eq!("Missing distance field", instruction_to_vec(Span::new("up")).unwrap_err().message);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_program("forward 5\n  down five").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 8, "five"));
+    }
+
+    // Found by fuzzing
+    #[test]
+    fn test_regressions() {
+        // Answers overflowed an i32, then an i64
+        let max = i32::MAX as i128;
+        let program = parse_program(&format!("forward {0}\ndown {0}\n", i32::MAX).repeat(200)).unwrap();
+        assert_eq!(run_program_p1(&program), (200 * max) * (200 * max));
+        assert_eq!(run_program_p2(&program), (200 * max) * (max * max * 199 * 200 / 2));
+        assert_eq!(Day02::stream(format!("forward {0}\ndown {0}\n", i32::MAX).repeat(200).as_bytes()).unwrap(),
+            (run_program_p1(&program), run_program_p2(&program)));
+        let program = parse_program("forward -2147483648\nup 2147483647\nforward 5\n").unwrap();
+        assert_eq!(run_program(program.into_iter()), ((5 - max - 1) * -max, (5 - max - 1) * -5 * max));
+
+        // Only programs whose answers really could overflow are rejected, where they get too long
+        let text = format!("forward {0}\ndown {0}\n", i32::MAX).repeat(3000);
+        let err = parse_program(&text).unwrap_err();
+        assert_eq!(err.message, "Program is too long, its answers could overflow an i128");
+        let Err(StreamError::Parse(stream_err)) = Day02::stream(text.as_bytes()) else { panic!("Expected an error") };
+        assert_eq!((stream_err.line, err.line), (5161, 5161));
+    }
+
+    #[test]
+    fn test_program() {
+        let sample = "forward 5
+            down 5
+            forward 8
+            up 3
+            down 8
+            forward 2";
+        let instructions = parse_program(sample).unwrap();
+        assert_eq!(150, run_program_p1(&instructions));
+        assert_eq!(900, run_program_p2(&instructions));
+        assert_eq!((150, 900), Day02::stream(sample.as_bytes()).unwrap());
+
+        let mut frames = Vec::new();
+        Day02::visualize(&instructions, &mut frames).unwrap();
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[5].caption, "Step 6/6: x = 15, depth = 60");
+        let Image::Grid(canvas) = &frames[5].image else { panic!("Expected a grid") };
+        assert_eq!(canvas[Point::new(99, 49)], 2);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_program(steps in prop::collection::vec((0..3usize, 0..100i32), 0..50)) {
+            let text = steps.iter()
+                .map(|(dir, n)| format!("{} {}\n", ["forward", "down", "up"][*dir], n))
+                .collect::<String>();
+            let program = parse_program(&text).unwrap();
+            let total = |dir| steps.iter().filter(|s| s.0 == dir).map(|s| s.1 as i128).sum::<i128>();
+            prop_assert_eq!(run_program_p1(&program), total(0) * (total(1) - total(2)));
+
+            // Without any forward moves the submarine never leaves the surface
+            let no_forward = program.iter().filter(|v| v.x == 0).cloned().collect::<Vec<Vec2>>();
+            prop_assert_eq!(run_program_p2(&no_forward), 0);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day02>();
+    }
+}
\ No newline at end of file
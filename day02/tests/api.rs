@@ -0,0 +1,11 @@
+use aoc::parse::Span;
+use aoc::Vec2;
+use day02::*;
+
+#[test]
+fn test_public_api() {
+    assert_eq!(instruction_to_vec(Span::new("down 8")), Ok(Vec2::new(0, 8)));
+    let program = parse_program("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2\n").unwrap();
+    assert_eq!(run_program(program.iter().cloned()), (150, 900));
+    assert_eq!(trace_program(program.into_iter()).nth(2), Some((5, 13, 40)));
+}
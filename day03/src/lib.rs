@@ -0,0 +1,248 @@
+//! Day 3: Binary Diagnostic. Rates the submarine from the most and least
+//! common bits of its diagnostic report.
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::{trace, ParseError, Solution};
+use rand::rngs::StdRng;
+
+fn bits_to_uint(bits: &[bool]) -> usize {
+    bits.iter().rev().zip(0..)
+        .map(|(x, index)| if *x { 1 << index } else { 0 })
+        .sum()
+}
+
+/// Parses the report as binary numbers, each a vector of digits.
+pub fn parse_bits(data: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    // All numbers must have the same width for the bit criteria to make sense
+    let data = Span::new(data);
+    let rows = data.digit_rows::<usize>(2)?;
+    if rows.is_empty() {
+        return Err(data.at_end().error("Expected at least one row"));
+    }
+    // Keeps gamma * epsilon within a usize
+    if rows[0].len() > 32 {
+        return Err(data.lines().next().unwrap().error(format!("Expected at most 32 bits, got {}", rows[0].len())));
+    }
+    Ok(rows)
+}
+
+/// Whether 1 is the most common digit at each position. Ties count as 1.
+pub fn most_common_bits(rows: &[Vec<usize>]) -> Vec<bool> {
+    let remainder = rows.len() % 2;
+    let threshold = (rows.len() - remainder) / 2 + remainder;
+
+    // Creates an iterator over vectors of digits, then folds them by adding
+    // each digit of each vector, and then thresholding them to
+    // output 0/1 depending on which was more common
+    rows.iter()
+        .fold(Vec::new(), |acc, x| {
+            if acc.is_empty() {
+                x.clone()
+            } else {
+                acc.iter().zip(x.iter()).map(|(a, b)| a + b).collect()
+            }
+        })
+        .iter().map(|v| *v >= threshold)
+        .collect()
+}
+
+/// Gamma rate times epsilon rate.
+pub fn power_consumption(rows: &[Vec<usize>]) -> usize {
+    let bits = most_common_bits(rows);
+
+    let gamma = bits_to_uint(&bits);
+    let epsilon = bits_to_uint(&bits.iter().map(|x| !*x).collect::<Vec<bool>>());
+    trace!("Gamma rate = {:0w$b} = {}, epsilon rate = {:0w$b} = {}", gamma, gamma, epsilon, epsilon, w = bits.len());
+    gamma * epsilon
+}
+
+/// Reduce according to the criteria which I cba to explain <https://adventofcode.com/2021/day/3>.
+/// `flip_bit` is used to enable the inverted criteria (least common bit).
+/// None if the criteria filter out every row, or run out of bits with several
+/// (duplicate) rows left.
+pub fn bit_criteria_reduce(mut rows: Vec<Vec<usize>>, flip_bit: bool) -> Option<usize> {
+    let mut bit_idx = 0;
+    while rows.len() > 1 && bit_idx < rows[0].len() {
+        let bits = most_common_bits(&rows);
+        let keep = (flip_bit ^ bits[bit_idx]) as usize;
+        // Only formatted when tracing is on
+        trace!("Bit {}: keeping {}s, removed {}", bit_idx, keep, format_rows(rows.iter().filter(|x| x[bit_idx] != keep)));
+        rows.retain(|x| x[bit_idx] == keep);
+        bit_idx += 1;
+    }
+    if rows.len() != 1 {
+        return None;
+    }
+    trace!("{} rating = {}", if flip_bit { "CO2 scrubber" } else { "Oxygen generator" }, format_rows(rows.iter()));
+    let bits = rows[0].iter().map(|x| *x > 0).collect::<Vec<bool>>();
+    Some(bits_to_uint(&bits))
+}
+
+fn format_rows<'a>(rows: impl Iterator<Item = &'a Vec<usize>>) -> String {
+    rows
+        .map(|row| row.iter().map(|d| d.to_string()).collect::<String>())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Oxygen generator rating times CO2 scrubber rating, or an error if either
+/// bit criteria don't narrow the numbers down to a single one.
+// Finding out takes the whole reduction, so it's left to part 2 rather than parsing
+pub fn life_support_rating(rows: &[Vec<usize>]) -> Result<usize, &'static str> {
+    let narrowed = "Bit criteria don't narrow the numbers down to a single one";
+    let oxygen_gen_rating = bit_criteria_reduce(rows.to_vec(), false).ok_or(narrowed)?;
+    let co2_scrub_rating = bit_criteria_reduce(rows.to_vec(), true).ok_or(narrowed)?;
+    Ok(oxygen_gen_rating * co2_scrub_rating)
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    type Input = Vec<Vec<usize>>;
+    type Answer1 = usize;
+    type Answer2 = Result<usize, &'static str>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_bits(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        power_consumption(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        life_support_rating(input)
+    }
+}
+
+// Numbers must be unique so the bit criteria always narrow down to a single
+// row, so the width grows with the number of rows
+impl Generator for Day03 {
+    const MIN_SIZE: usize = 1;
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let width = 12.max(usize::BITS - (size - 1).leading_zeros() + 1) as usize;
+        let mut rows = rand::seq::index::sample(rng, 1 << width, size).into_vec();
+
+        // The least common bit criteria find no rows if the remaining rows all
+        // agree on a bit, so follow the CO2 scrubber reduction and flip that
+        // bit in one of them. It keeps the rows unique, as the remaining rows
+        // share a prefix that no other row has
+        let mut remaining = (0..rows.len()).collect::<Vec<usize>>();
+        for bit in (0..width).rev() {
+            if remaining.len() <= 1 {
+                break;
+            }
+            let count_ones = |rows: &[usize], remaining: &[usize]| remaining.iter().filter(|i| rows[**i] >> bit & 1 == 1).count();
+            let mut ones = count_ones(&rows, &remaining);
+            if ones == 0 || ones == remaining.len() {
+                rows[remaining[0]] ^= 1 << bit;
+                ones = count_ones(&rows, &remaining);
+            }
+            let least_common = if ones * 2 >= remaining.len() { 0 } else { 1 };
+            remaining.retain(|i| rows[*i] >> bit & 1 == least_common);
+        }
+
+        rows.iter()
+            .map(|n| format!("{:0width$b}\n", n, width = width))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let sample = "00100
+            11110
+            10110
+            10111
+            10101
+            01111
+            00111
+            11100
+            10000
+            11001
+            00010
+            01010";
+        let rows = parse_bits(sample).unwrap();
+        assert_eq!(198, power_consumption(&rows));
+        assert_eq!(Ok(230), life_support_rating(&rows));
+
+        let (_, steps) = aoc::trace::capture(|| bit_criteria_reduce(rows.clone(), false));
+        assert_eq!(steps, [
+            "Bit 0: keeping 1s, removed 00100, 01111, 00111, 00010, 01010",
+            "Bit 1: keeping 0s, removed 11110, 11100, 11001",
+            "Bit 2: keeping 1s, removed 10000",
+            "Bit 3: keeping 1s, removed 10101",
+            "Bit 4: keeping 1s, removed 10110",
+            "Oxygen generator rating = 10111",
+        ]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_bits("00100\n11210\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "2"));
+        let err = parse_bits("00100\n1110\n").unwrap_err();
+        assert_eq!(err.message, "Expected 5 digits, got 4");
+        assert_eq!(parse_bits("\n").unwrap_err().message, "Expected at least one row");
+    }
+
+    // Found by fuzzing
+    #[test]
+    fn test_regressions() {
+        // The bit criteria found no rows, or ran past the last bit with duplicate rows left
+        for report in ["1\n1\n", "0101\n0101\n1100\n"] {
+            let rows = parse_bits(report).unwrap();
+            assert_eq!(life_support_rating(&rows), Err("Bit criteria don't narrow the numbers down to a single one"));
+        }
+        let day = aoc::Day::of::<Day03>();
+        assert_eq!(day.solve(2, &day.parse("1\n1\n").unwrap()), Err("Bit criteria don't narrow the numbers down to a single one".to_string()));
+        // Gamma * epsilon overflowed
+        let err = parse_bits(&format!("{}\n", "1".repeat(40))).unwrap_err();
+        assert_eq!(err.message, "Expected at most 32 bits, got 40");
+    }
+
+    fn rows() -> impl Strategy<Value = Vec<Vec<usize>>> {
+        (1..12usize).prop_flat_map(|width|
+            prop::collection::vec(prop::collection::vec(0..2usize, width), 1..50))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_most_common_bits(rows in rows()) {
+            let bits = most_common_bits(&rows);
+            for (i, bit) in bits.iter().enumerate() {
+                let ones = rows.iter().filter(|r| r[i] == 1).count();
+                prop_assert_eq!(*bit, ones * 2 >= rows.len());
+            }
+        }
+
+        // Epsilon is gamma with every bit flipped
+        #[test]
+        fn prop_power_consumption(rows in rows()) {
+            let gamma = bits_to_uint(&most_common_bits(&rows));
+            let mask = (1 << rows[0].len()) - 1;
+            prop_assert_eq!(power_consumption(&rows), gamma * (mask ^ gamma));
+
+            let text = rows.iter()
+                .map(|r| r.iter().map(|b| b.to_string()).collect::<String>() + "\n")
+                .collect::<String>();
+            prop_assert_eq!(parse_bits(&text).unwrap(), rows);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day03>();
+        let day = aoc::Day::generated::<Day03>();
+        assert_eq!(day.generate(0, 0), Some(Err("Inputs need at least 1 record(s), got 0".to_string())));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use day03::*;
+
+#[test]
+fn test_public_api() {
+    let rows = parse_bits("00100\n11110\n10110\n10111\n10101\n01111\n00111\n11100\n10000\n11001\n00010\n01010\n").unwrap();
+    assert_eq!(most_common_bits(&rows), [true, false, true, true, false]);
+    assert_eq!(power_consumption(&rows), 198);
+    assert_eq!(bit_criteria_reduce(rows.clone(), false), Some(23));
+    assert_eq!(bit_criteria_reduce(rows.clone(), true), Some(10));
+    assert_eq!(life_support_rating(&rows), Ok(230));
+}
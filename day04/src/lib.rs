@@ -0,0 +1,393 @@
+//! Day 4: Giant Squid. Finding the first and last bingo cards to win.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::vis::{Frame, Image, Sink, Visualize};
+use aoc::{trace, Grid, ParseError, Point, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+const BOARD_WIDTH: usize = 5;
+
+/// A 5x5 bingo card of unique numbers.
+#[derive(Debug)]
+pub struct BingoCard {
+    grid: Grid<u32>,
+    // List of rows and collumns
+    lines: Vec<HashSet<u32>>,
+}
+
+impl BingoCard {
+    /// Parses the five rows of a card.
+    pub fn parse(contents: Span) -> Result<Self, ParseError> {
+        // Parse into 5x5 grid
+        let grid = Grid::<u32>::parse_words(contents)?;
+
+        // Precondition: all rows are correct width
+        let Some(first_row) = contents.lines().next() else {
+            return Err(contents.error(format!("Expected {} board rows, got 0", BOARD_WIDTH)));
+        };
+        if grid.width() != BOARD_WIDTH {
+            return Err(first_row.error(
+                format!("Expected {} numbers in board row, got {}", BOARD_WIDTH, grid.width())));
+        }
+        if grid.height() != BOARD_WIDTH {
+            return Err(contents.error(
+                format!("Expected {} board rows, got {}", BOARD_WIDTH, grid.height())));
+        }
+
+        // Create sets for the rows and columns
+        let lines = grid
+            .rows()
+            .map(|row| HashSet::from_iter(row.iter().cloned()))
+            .chain(grid.columns().map(|column| HashSet::from_iter(column.cloned())))
+            // Precondition: no repeated numbers
+            .filter(|set| set.len() == BOARD_WIDTH)
+            .collect::<Vec<HashSet<u32>>>();
+
+        // Precondition: no repeated numbers
+        if lines.len() != 2 * BOARD_WIDTH {
+            return Err(contents.error("Numbers in board rows/columns not unique"));
+        }
+
+        Ok(Self {
+            grid,
+            lines,
+        })
+    }
+
+    pub fn numbers(&self) -> &Grid<u32> {
+        &self.grid
+    }
+
+    /// Whether every number of a row or column has been called.
+    pub fn is_bingo(&self, called: &HashSet<u32>) -> bool {
+        for line in self.lines.iter() {
+            if line.is_subset(called) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Sum of the numbers which haven't been called.
+    pub fn unmarked_sum(&self, called: &HashSet<u32>) -> u64 {
+        self.grid
+            .iter()
+            .filter(|n| !called.contains(n))
+            .map(|n| *n as u64)
+            .sum()
+    }
+}
+
+impl fmt::Display for BingoCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
+
+/// Parses the drawn numbers and the cards.
+pub fn parse_input(contents: &str) -> Result<(Vec<u32>, Vec<BingoCard>), ParseError> {
+    let contents = Span::new(contents);
+    let mut blocks = contents.blocks();
+
+    let drawn = blocks.next()
+        .ok_or_else(|| contents.at_end().error("Expected list of drawn numbers"))?
+        .list::<u32>(',')?;
+
+    let bingo_cards = blocks
+        .map(BingoCard::parse)
+        .collect::<Result<Vec<BingoCard>, ParseError>>()?;
+    if bingo_cards.is_empty() {
+        return Err(contents.at_end().error("Expected at least one bingo card"));
+    }
+    Ok((drawn, bingo_cards))
+}
+
+/// Score of the first card to win: its unmarked sum times the winning number.
+/// An error if no card wins.
+pub fn p1_winning_board_score(drawn: &[u32], boards: &[BingoCard]) -> Result<u128, &'static str> {
+    let mut called = HashSet::<u32>::from_iter(drawn.iter().take(BOARD_WIDTH - 1).cloned());
+    // called contains first 4 elements
+    // so we can start each iteration by adding the next number
+    for num in drawn.iter().skip(BOARD_WIDTH - 1) {
+        called.insert(*num);
+
+        for (i, board) in boards.iter().enumerate() {
+            if board.is_bingo(&called) {
+                let unmarked_sum = board.unmarked_sum(&called);
+                trace!("Drew {}: board {} wins first, unmarked sum {}", num, i + 1, unmarked_sum);
+                return Ok(unmarked_sum as u128 * *num as u128);
+            }
+        }
+    }
+
+    Err("No card ever wins")
+
+    // For each board, form the set of sets of rows and columns
+    // Then iterate over each successively bigger set of input numbers
+    // (starting at 5 - the minimum required for a win) and look
+    // for the set with a row/column set which is a subset of the input set
+
+    // For the score, the unmarked numbers can be found by a union of all
+    // row/column sets intersected with called numbers
+
+    // The above implies uniqueness is a constraint
+    // - cant imagine why a bingo board wouldnt be unique
+    // Even so, this precondition must be asserted on set creation using the set sizes
+}
+
+/// Score of the last card to win. An error if some card never wins, as then
+/// there's no last winner.
+pub fn p2_losing_board_score(drawn: &[u32], boards: &[BingoCard]) -> Result<u128, &'static str> {
+    let mut boards = boards.iter().enumerate().collect::<Vec<(usize, &BingoCard)>>();
+    let mut called = HashSet::<u32>::from_iter(drawn.iter().take(BOARD_WIDTH - 1).cloned());
+
+    for num in drawn.iter().skip(BOARD_WIDTH - 1) {
+        called.insert(*num);
+
+        // Have to use old fashion iterating
+        // in order to remove from list while iterating
+        let mut i = 0;
+        while i < boards.len() {
+            let (index, board) = boards[i];
+            if board.is_bingo(&called) {
+                trace!("Drew {}: board {} wins, {} left", num, index + 1, boards.len() - 1);
+                if boards.len() == 1 {
+                    let unmarked_sum = board.unmarked_sum(&called);
+                    trace!("Board {} wins last, unmarked sum {}", index + 1, unmarked_sum);
+                    return Ok(unmarked_sum as u128 * *num as u128);
+                } else {
+                    boards.remove(i);
+                }
+            } else {
+                i += 1;
+            }
+        }
+    }
+
+    Err("Some card never wins")
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u32 = 4;
+
+    type Input = (Vec<u32>, Vec<BingoCard>);
+    // Up to 25 numbers near u32::MAX times another one needs more than a u64
+    type Answer1 = Result<u128, &'static str>;
+    type Answer2 = Result<u128, &'static str>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        p1_winning_board_score(&input.0, &input.1)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        p2_losing_board_score(&input.0, &input.1)
+    }
+}
+
+// Cards are tiled in a square, unmarked numbers are dim, marked ones are
+// brighter, and the brightest are on cards that have won
+impl Visualize for Day04 {
+    fn visualize(input: &Self::Input, sink: &mut dyn Sink) -> io::Result<()> {
+        let (drawn, boards) = input;
+        let stride = BOARD_WIDTH + 1;
+        let columns = (1..).find(|c| c * c >= boards.len()).unwrap();
+        let rows = boards.len().div_ceil(columns);
+        let mut image = Grid::new(columns * stride - 1, rows * stride - 1, 0);
+
+        let mut called = HashSet::new();
+        for (i, num) in drawn.iter().enumerate() {
+            called.insert(*num);
+            let mut won = 0;
+            for (b, board) in boards.iter().enumerate() {
+                let bingo = board.is_bingo(&called);
+                won += bingo as usize;
+                let corner = Point::new(((b % columns) * stride) as i32, ((b / columns) * stride) as i32);
+                for pos in board.grid.positions() {
+                    image[corner + pos] = match (called.contains(&board.grid[pos]), bingo) {
+                        (false, _) => 1,
+                        (true, false) => 2,
+                        (true, true) => 3,
+                    };
+                }
+            }
+            let caption = format!("Drew {} ({}/{}): {}/{} cards won", num, i + 1, drawn.len(), won, boards.len());
+            sink.frame(Frame { caption, image: Image::Grid(image.clone()) })?;
+            if won == boards.len() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Every number is drawn eventually, so every card wins and there is always a last winner
+impl Generator for Day04 {
+    // The parser needs a card
+    const MIN_SIZE: usize = 1;
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let mut drawn = (0..100).collect::<Vec<u32>>();
+        drawn.shuffle(rng);
+        let mut out = drawn.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(",");
+        out.push('\n');
+
+        let mut cards = HashSet::new();
+        while cards.len() < size {
+            let numbers = rand::seq::index::sample(rng, drawn.len(), BOARD_WIDTH * BOARD_WIDTH)
+                .into_iter()
+                .map(|n| n as u32)
+                .collect::<Vec<u32>>();
+            if !cards.insert(numbers.clone()) {
+                continue;
+            }
+            let rows = numbers.chunks(BOARD_WIDTH).map(|row| row.to_vec()).collect();
+            out.push('\n');
+            out += &Grid::from_rows(rows).unwrap().to_string();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_basic() {
+        let sample = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+8  2 23  4 24
+21  9 14 16  7
+6 10  3 18  5
+1 12 20 15 19
+
+3 15  0  2 22
+9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+2  0 12  3  7";
+
+        let (drawn, boards) = parse_input(sample).unwrap();
+        assert_eq!(drawn[0..3], [7, 4, 9]);
+        assert_eq!(boards.len(), 3);
+        assert_eq!(boards[0].lines.len(), 10);
+        assert_eq!(boards[0].lines[5], HashSet::from([22, 8, 21, 6, 1]));
+
+        assert_eq!(p1_winning_board_score(&drawn, &boards), Ok(4512));
+        assert_eq!(p2_losing_board_score(&drawn, &boards), Ok(1924));
+        let (_, steps) = aoc::trace::capture(|| p1_winning_board_score(&drawn, &boards));
+        assert_eq!(steps, ["Drew 24: board 3 wins first, unmarked sum 188"]);
+
+        // The last card wins on the 15th number, 13
+        let mut frames = Vec::new();
+        Day04::visualize(&(drawn, boards), &mut frames).unwrap();
+        assert_eq!(frames.len(), 15);
+        assert_eq!(frames[14].caption, "Drew 13 (15/27): 3/3 cards won");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("7,4,9\n\n22 13 17 11  0\n8  2 23  4").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (4, 1, "8  2 23  4"));
+        assert_eq!(err.message, "Expected 5 cells in row, got 4");
+        let err = parse_input("7,4,9\n\n22 13 17 11\n8  2 23  4").unwrap_err();
+        assert_eq!(err.message, "Expected 5 numbers in board row, got 4");
+
+        let err = parse_input("7,4,x").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 5, "x"));
+
+        assert_eq!(parse_input("").unwrap_err().message, "Expected list of drawn numbers");
+
+        // Empty cards have no first row to point at
+        for card in ["", " \n\t "] {
+            let err = BingoCard::parse(Span::new(card)).unwrap_err();
+            assert_eq!((err.line, err.column, err.message.as_str()), (1, 1, "Expected 5 board rows, got 0"));
+        }
+    }
+
+    // Found by fuzzing
+    #[test]
+    fn test_regressions() {
+        let card = "1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25";
+        // Nobody won
+        let (drawn, boards) = parse_input(&format!("1,2,3\n\n{}", card)).unwrap();
+        assert_eq!(p1_winning_board_score(&drawn, &boards), Err("No card ever wins"));
+        assert_eq!(p2_losing_board_score(&drawn, &boards), Err("Some card never wins"));
+        let (drawn, boards) = parse_input(&format!("1,2,3,4,5\n\n{}\n\n{}", card, card.replacen("1 2", "26 2", 1))).unwrap();
+        assert_eq!(p1_winning_board_score(&drawn, &boards), Ok((325 - 15) * 5));
+        assert_eq!(p2_losing_board_score(&drawn, &boards), Err("Some card never wins"));
+        let err = parse_input("1,2,3\n").unwrap_err();
+        assert_eq!(err.message, "Expected at least one bingo card");
+        // Repeated numbers left fewer lines than expected
+        let err = parse_input(&format!("1,2,3,4,5\n\n{}", card.replace("25", "24"))).unwrap_err();
+        assert_eq!(err.message, "Numbers in board rows/columns not unique");
+        // Scores overflowed a u32, then a u64
+        let (drawn, boards) = parse_input(&format!("1,2,3,4,65535\n\n{}", card.replace(" 5\n", " 65535\n"))).unwrap();
+        assert_eq!(p1_winning_board_score(&drawn, &boards), Ok((325 - 15) * 65535));
+        let big = (0..25).map(|i| u32::MAX - i).collect::<Vec<u32>>();
+        let (drawn, boards) = parse_input(&format!("{}\n\n{}", big[20..].iter().map(|n| n.to_string()).collect::<Vec<String>>().join(","),
+            card_text(&big))).unwrap();
+        let unmarked = big[..20].iter().map(|n| *n as u128).sum::<u128>();
+        assert_eq!(p1_winning_board_score(&drawn, &boards), Ok(unmarked * big[24] as u128));
+        assert_eq!(p2_losing_board_score(&drawn, &boards), Ok(unmarked * big[24] as u128));
+    }
+
+    fn card_numbers() -> impl Strategy<Value = Vec<u32>> {
+        prop::sample::subsequence((0..100).collect::<Vec<u32>>(), BOARD_WIDTH * BOARD_WIDTH).prop_shuffle()
+    }
+
+    fn card_text(numbers: &[u32]) -> String {
+        Grid::from_rows(numbers.chunks(BOARD_WIDTH).map(|r| r.to_vec()).collect()).unwrap().to_string()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_card_round_trip(numbers in card_numbers()) {
+            let text = card_text(&numbers);
+            let card = BingoCard::parse(Span::new(&text)).unwrap();
+            prop_assert_eq!(card.to_string(), text);
+            prop_assert_eq!(card.grid.iter().cloned().collect::<Vec<u32>>(), numbers);
+        }
+
+        // Transposing swaps rows with columns, so every card wins at the same time
+        #[test]
+        fn prop_transposed_scores(
+            cards in prop::collection::vec(card_numbers(), 1..5),
+            drawn in Just((0..100).collect::<Vec<u32>>()).prop_shuffle(),
+        ) {
+            let cards = cards.iter()
+                .map(|numbers| BingoCard::parse(Span::new(&card_text(numbers))).unwrap())
+                .collect::<Vec<BingoCard>>();
+            let transposed = cards.iter()
+                .map(|card| BingoCard::parse(Span::new(&card.grid.transpose().to_string())).unwrap())
+                .collect::<Vec<BingoCard>>();
+            prop_assert_eq!(p1_winning_board_score(&drawn, &cards), p1_winning_board_score(&drawn, &transposed));
+            prop_assert_eq!(p2_losing_board_score(&drawn, &cards), p2_losing_board_score(&drawn, &transposed));
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day04>();
+    }
+}
@@ -0,0 +1,18 @@
+use std::collections::HashSet;
+
+use aoc::parse::Span;
+use day04::*;
+
+#[test]
+fn test_public_api() {
+    let card = BingoCard::parse(Span::new("14 21 17 24  4\n10 16 15  9 19\n18  8 23 26 20\n22 11 13  6  5\n 2  0 12  3  7\n")).unwrap();
+    assert_eq!(card.numbers().row(0), [14, 21, 17, 24, 4]);
+
+    let called = HashSet::from([7, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24]);
+    assert!(card.is_bingo(&called));
+    assert!(!card.is_bingo(&HashSet::from([14, 21, 17, 24])));
+    assert_eq!(card.unmarked_sum(&called), 188);
+
+    let err = BingoCard::parse(Span::new("1 2 3 4 5\n")).unwrap_err();
+    assert_eq!(err.message, "Expected 5 board rows, got 1");
+}
@@ -0,0 +1,297 @@
+//! Day 5: Hydrothermal Venture. Finding where lines of vents overlap.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead};
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::stream::{self, StreamError, Streaming};
+use aoc::vis::{Frame, Image, Sink, Visualize};
+use aoc::{trace, Grid, ParseError, Point, Solution, Vec2};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// A horizontal, vertical or diagonal line of vents, between two points on the grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Line(pub Point, pub Point);
+
+impl Line {
+    pub fn is_axis_aligned(&self) -> bool {
+        self.0.x == self.1.x || self.0.y == self.1.y
+    }
+
+    /// Unit step from the start to the end. Note that this only works for axis
+    /// aligned lines and diagonal lines. Others return None.
+    pub fn direction(&self) -> Option<Vec2> {
+        let diff = self.1 - self.0;
+        if diff.x == 0 || diff.y == 0 || diff.x.abs() == diff.y.abs() {
+            Some(diff.signum())
+        } else {
+            None
+        }
+    }
+
+    /// Adds 1 to every point of `touched` on the line.
+    pub fn trace(&self, touched: &mut Coverage) {
+        for pos in self.0.line_to(self.1) {
+            match touched {
+                Coverage::Grid { touched, origin } => touched[pos + *origin] += 1,
+                Coverage::Sparse(touched) => *touched.entry(pos).or_insert(0) += 1,
+            }
+        }
+    }
+}
+
+/// Most points to count on a grid. Lines spread out any further are counted in
+/// a map of just the points they cover.
+const MAX_GRID_AREA: i64 = 1 << 24;
+
+/// How many lines cover each point.
+pub enum Coverage {
+    /// Grid over (0, 0) and every line, with (0, 0) at `origin` on it.
+    Grid { touched: Grid<usize>, origin: Vec2 },
+    Sparse(HashMap<Point, usize>),
+}
+
+impl Coverage {
+    /// Room for `lines`, on a grid unless it would be too big.
+    pub fn new<'a>(lines: impl Iterator<Item = &'a Line>) -> Self {
+        let (min, max) = lines.flat_map(|l| [l.0, l.1]).fold((Point::ZERO, Point::ZERO), |(min, max), p| {
+            (Point::new(min.x.min(p.x), min.y.min(p.y)), Point::new(max.x.max(p.x), max.y.max(p.y)))
+        });
+        let width = max.x as i64 - min.x as i64 + 1;
+        let height = max.y as i64 - min.y as i64 + 1;
+        if width * height > MAX_GRID_AREA {
+            return Coverage::Sparse(HashMap::new());
+        }
+        Coverage::Grid { touched: Grid::new(width as usize, height as usize, 0), origin: -min }
+    }
+
+    /// Number of points covered by at least two lines.
+    pub fn overlaps(&self) -> usize {
+        match self {
+            Coverage::Grid { touched, .. } => touched.iter().filter(|v| **v > 1).count(),
+            Coverage::Sparse(touched) => touched.values().filter(|v| **v > 1).count(),
+        }
+    }
+}
+
+/// Parses a line such as `0,9 -> 5,9`.
+pub fn parse_line(line: Span) -> Result<Line, ParseError> {
+    let (x1, y1, x2, y2) = line.tuple::<(i32, i32, i32, i32)>()?;
+    // Keeps the steps between the ends within an i32
+    if (x1 as i64 - x2 as i64).abs() > i32::MAX as i64 || (y1 as i64 - y2 as i64).abs() > i32::MAX as i64 {
+        return Err(line.error("Line segment is too long"));
+    }
+    let parsed = Line(Point::new(x1, y1), Point::new(x2, y2));
+    if parsed.direction().is_none() {
+        return Err(line.error("Line segment must be horizontal, vertical or diagonal"));
+    }
+    Ok(parsed)
+}
+
+/// Parses one line per line.
+pub fn parse_lines(contents: &str) -> Result<Vec<Line>, ParseError> {
+    Span::new(contents).lines()
+        .map(parse_line)
+        .collect()
+}
+
+/// Number of points covered by at least two of `lines`.
+pub fn count_overlapping_points<'a, I>(lines: I) -> usize
+    where I: Iterator<Item = &'a Line>
+{
+    let lines = lines.collect::<Vec<&Line>>();
+    let mut touched = Coverage::new(lines.iter().copied());
+    for line in lines {
+        line.trace(&mut touched);
+        trace!("{} -> {}: {} points, {} overlaps so far", line.0, line.1,
+            line.0.chebyshev(line.1) + 1, touched.overlaps());
+    }
+    touched.overlaps()
+}
+
+fn p1_overlap_aa_lines(lines: &[Line]) -> usize {
+    count_overlapping_points(lines.iter().filter(|l| l.is_axis_aligned()))
+}
+
+fn p2_overlap_aa_and_diag_lines(lines: &[Line]) -> usize {
+    count_overlapping_points(lines.iter())
+}
+
+/// Both parts in a single pass. The lines aren't known up front to size a
+/// grid, so points are counted in maps, which only grow with the points covered.
+pub fn count_overlaps_streaming(lines: impl Iterator<Item = Line>) -> (usize, usize) {
+    let mut axis_aligned = Coverage::Sparse(HashMap::new());
+    let mut all = Coverage::Sparse(HashMap::new());
+    for line in lines {
+        if line.is_axis_aligned() {
+            line.trace(&mut axis_aligned);
+        }
+        line.trace(&mut all);
+    }
+    (axis_aligned.overlaps(), all.overlaps())
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    type Input = Vec<Line>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        p1_overlap_aa_lines(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        p2_overlap_aa_and_diag_lines(input)
+    }
+}
+
+impl Streaming for Day05 {
+    fn stream<R: BufRead>(reader: R) -> Result<(Self::Answer1, Self::Answer2), StreamError> {
+        let lines = stream::parse_lines(reader, parse_line);
+        stream::process_results(lines, |lines| count_overlaps_streaming(lines))
+    }
+}
+
+const FRAMES: usize = 50;
+
+// Traces every line, as in part 2, showing how many times each point is covered
+impl Visualize for Day05 {
+    fn visualize(input: &Self::Input, sink: &mut dyn Sink) -> io::Result<()> {
+        let mut touched = Coverage::new(input.iter());
+        if let Coverage::Sparse(_) = touched {
+            return Err(io::Error::other("Lines are too far apart to draw"));
+        }
+        let every = input.len().div_ceil(FRAMES).max(1);
+        for (i, line) in input.iter().enumerate() {
+            line.trace(&mut touched);
+            if let Coverage::Grid { touched: grid, .. } = &touched {
+                if (i + 1) % every == 0 || i + 1 == input.len() {
+                    let caption = format!("Traced {}/{} lines: {} overlaps", i + 1, input.len(), touched.overlaps());
+                    sink.frame(Frame { caption, image: Image::Grid(grid.map(|v| *v as u32)) })?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const GRID_SIZE: i32 = 1000;
+
+// Segments are horizontal, vertical or diagonal, at least one step long and
+// inside the grid. Any other ones are rejected and tried again
+impl Generator for Day05 {
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let on_grid = |p: Point| (0..GRID_SIZE).contains(&p.x) && (0..GRID_SIZE).contains(&p.y);
+        let mut out = String::new();
+        let mut count = 0;
+        while count < size {
+            let start = Point::new(rng.random_range(0..GRID_SIZE), rng.random_range(0..GRID_SIZE));
+            let dir = Vec2::new(rng.random_range(-1..=1), rng.random_range(-1..=1));
+            let end = start + dir * rng.random_range(1..GRID_SIZE);
+            if dir != Vec2::ZERO && on_grid(end) {
+                out += &format!("{},{} -> {},{}\n", start.x, start.y, end.x, end.y);
+                count += 1;
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let sample = "0,9 -> 5,9
+            8,0 -> 0,8
+            9,4 -> 3,4
+            2,2 -> 2,1
+            7,0 -> 7,4
+            6,4 -> 2,0
+            0,9 -> 2,9
+            3,4 -> 1,4
+            0,0 -> 8,8
+            5,5 -> 8,2";
+        
+        let lines = parse_lines(sample).unwrap();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], Line(Point::new(0, 9), Point::new(5, 9)));
+        assert_eq!(lines[9], Line(Point::new(5, 5), Point::new(8, 2)));
+
+        assert_eq!(p1_overlap_aa_lines(&lines), 5);
+        assert_eq!(p2_overlap_aa_and_diag_lines(&lines), 12);
+        assert_eq!(Day05::stream(sample.as_bytes()).unwrap(), (5, 12));
+
+        let mut frames = Vec::new();
+        Day05::visualize(&lines, &mut frames).unwrap();
+        assert_eq!(frames.len(), 10);
+        assert_eq!(frames[9].caption, "Traced 10/10 lines: 12 overlaps");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_lines("0,9 -> 5,9\n8,0 -> 0\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "8,0 -> 0"));
+        let err = parse_lines("0,9 -> 5,9\n8,0 -> 99999999999,8\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 8, "99999999999"));
+        let err = parse_lines("0,0 -> 1,2").unwrap_err();
+        assert_eq!(err.message, "Line segment must be horizontal, vertical or diagonal");
+    }
+
+    // Found by fuzzing
+    #[test]
+    fn test_regressions() {
+        // Point segments divided by zero finding their direction
+        let lines = parse_lines("1,1 -> 1,1\n1,1 -> 1,1\n0,0 -> 2,2").unwrap();
+        assert_eq!(p1_overlap_aa_lines(&lines), 1);
+        assert_eq!(p2_overlap_aa_and_diag_lines(&lines), 1);
+        // Huge coordinates needed a huge grid
+        let lines = parse_lines("0,0 -> 100000,0\n0,0 -> 0,-100000\n").unwrap();
+        assert!(matches!(Coverage::new(lines.iter()), Coverage::Sparse(_)));
+        assert_eq!(p1_overlap_aa_lines(&lines), 1);
+        let err = parse_lines("-2000000000,0 -> 2000000000,0").unwrap_err();
+        assert_eq!(err.message, "Line segment is too long");
+    }
+
+    fn segment() -> impl Strategy<Value = Line> {
+        (-50..50i32, -50..50i32, -1..=1i32, -1..=1i32, 0..20i32)
+            .prop_map(|(x, y, dx, dy, len)| {
+                let start = Point::new(x, y);
+                Line(start, start + Vec2::new(dx, dy) * len)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn prop_swapped_endpoints(lines in prop::collection::vec(segment(), 0..30)) {
+            let swapped = lines.iter().map(|l| Line(l.1, l.0)).collect::<Vec<Line>>();
+            prop_assert_eq!(p1_overlap_aa_lines(&lines), p1_overlap_aa_lines(&swapped));
+            prop_assert_eq!(p2_overlap_aa_and_diag_lines(&lines), p2_overlap_aa_and_diag_lines(&swapped));
+        }
+
+        #[test]
+        fn prop_parse(lines in prop::collection::vec(segment(), 0..30)) {
+            let text = lines.iter()
+                .map(|l| format!("{},{} -> {},{}\n", l.0.x, l.0.y, l.1.x, l.1.y))
+                .collect::<String>();
+            prop_assert_eq!(parse_lines(&text).unwrap(), lines);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day05>();
+    }
+}
\ No newline at end of file
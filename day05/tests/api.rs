@@ -0,0 +1,23 @@
+use aoc::parse::Span;
+use aoc::{Point, Vec2};
+use day05::*;
+
+#[test]
+fn test_public_api() {
+    let line = parse_line(Span::new("8,0 -> 0,8")).unwrap();
+    assert_eq!(line, Line(Point::new(8, 0), Point::new(0, 8)));
+    assert_eq!(line.direction(), Some(Vec2::new(-1, 1)));
+    assert!(!line.is_axis_aligned());
+
+    let mut touched = Coverage::new([&line].into_iter());
+    line.trace(&mut touched);
+    line.trace(&mut touched);
+    assert_eq!(touched.overlaps(), 9);
+    let Coverage::Grid { touched, origin } = touched else { panic!("Expected a grid") };
+    assert_eq!((origin, touched.anti_diagonal().collect::<Vec<_>>()), (Vec2::ZERO, vec![&2; 9]));
+
+    let lines = parse_lines("0,9 -> 5,9\n0,9 -> 2,9\n").unwrap();
+    assert_eq!(count_overlapping_points(lines.iter()), 3);
+    let lines = parse_lines("-3,-1 -> 1,3\n-3,3 -> 1,-1\n").unwrap();
+    assert_eq!(count_overlapping_points(lines.iter()), 1);
+}
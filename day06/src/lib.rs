@@ -0,0 +1,154 @@
+//! Day 6: Lanternfish. Counting a population of fish which breed every week.
+
+use std::io;
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::vis::{Frame, Image, Sink, Visualize};
+use aoc::{trace, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Parses the comma separated ages of the starting fish, which are 0-6.
+pub fn parse_fish_ages(input: &str) -> Result<Vec<u32>, ParseError> {
+    Span::new(input).fields(',')
+        .map(|s| {
+            let age = s.number::<u32>()?;
+            if age >= 7 {
+                return Err(s.error(format!("Invalid fish age {} (must be 0-6)", age)));
+            }
+            Ok(age)
+        })
+        .collect()
+}
+
+/// Number of fish of each age, from a list of fish ages. Panics on ages above 6.
+pub fn make_fish_counts(fishes: &[u32]) -> [usize; 9] {
+    let mut fish_counts = [0; 9];
+    for fish in fishes {
+        assert!(*fish < 7);
+        fish_counts[*fish as usize] += 1;
+    }
+    fish_counts
+}
+
+/// Simulates fish described in counts by age for `days`:
+/// `fish_counts` represents list where index is age (0-8) and value is
+/// count of fish that age.
+pub fn simulate(mut fish_counts: [usize; 9], days: usize) -> [usize; 9] {
+    for day in 1..=days {
+        let mut next_counts = [0; 9];
+        next_counts[..8].copy_from_slice(&fish_counts[1..]);
+
+        // Each fish spawns a new one when it hits 0.
+        next_counts[6] = next_counts[6].checked_add(fish_counts[0]).unwrap();
+        next_counts[8] = next_counts[8].checked_add(fish_counts[0]).unwrap();
+
+        fish_counts = next_counts;
+        trace!("Day {}: {:?}, {} fish", day, fish_counts, fish_counts.iter().sum::<usize>());
+    }
+    fish_counts
+}
+
+/// Total number of fish after `days`.
+pub fn simulate_count(fish_counts: [usize; 9], days: usize) -> usize {
+    simulate(fish_counts, days).iter().sum()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+
+    type Input = [usize; 9];
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_fish_ages(input).map(|fishes| make_fish_counts(&fishes))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        simulate_count(*input, 80)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        simulate_count(*input, 256)
+    }
+}
+
+// Fish counts by age, every day up to the end of part 2
+impl Visualize for Day06 {
+    fn visualize(input: &Self::Input, sink: &mut dyn Sink) -> io::Result<()> {
+        let mut fish_counts = *input;
+        for day in 0..=256 {
+            let caption = format!("Day {}: {} fish", day, fish_counts.iter().sum::<usize>());
+            let bars = fish_counts.iter().enumerate().map(|(age, n)| (age.to_string(), *n as u64)).collect();
+            sink.frame(Frame { caption, image: Image::Chart(bars) })?;
+            fish_counts = simulate(fish_counts, 1);
+        }
+        Ok(())
+    }
+}
+
+impl Generator for Day06 {
+    const MIN_SIZE: usize = 1;
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let ages = (0..size)
+            .map(|_| rng.random_range(0..7u32).to_string())
+            .collect::<Vec<String>>();
+        ages.join(",") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test() {
+        let fish_counts = make_fish_counts(&parse_fish_ages("3,4,3,1,2").unwrap());
+        assert_eq!(simulate_count(fish_counts, 18), 26);
+        assert_eq!(simulate_count(fish_counts, 80), 5934);
+        let (_, steps) = aoc::trace::capture(|| simulate(fish_counts, 2));
+        assert_eq!(steps, ["Day 1: [1, 1, 2, 1, 0, 0, 0, 0, 0], 5 fish", "Day 2: [1, 2, 1, 0, 0, 0, 1, 0, 1], 6 fish"]);
+
+        let mut frames = Vec::new();
+        Day06::visualize(&fish_counts, &mut frames).unwrap();
+        assert_eq!(frames.len(), 257);
+        assert_eq!(frames[80].caption, "Day 80: 5934 fish");
+        assert_eq!(frames[0].image, Image::Chart((0..9).map(|age| (age.to_string(), [0, 1, 1, 2, 1, 0, 0, 0, 0][age])).collect()));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_fish_ages("3,4,9,1").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 5, "9"));
+        assert_eq!(err.message, "Invalid fish age 9 (must be 0-6)");
+    }
+
+    proptest! {
+        // Fish never die, so every day has at least as many as the day before
+        #[test]
+        fn prop_simulate_keeps_fish(ages in prop::collection::vec(0..7u32, 0..300), days in 0..100usize) {
+            let counts = make_fish_counts(&ages);
+            prop_assert_eq!(simulate_count(counts, 0), ages.len());
+            let today = simulate_count(counts, days);
+            prop_assert!(today >= ages.len());
+            prop_assert!(simulate_count(counts, days + 1) >= today);
+        }
+
+        #[test]
+        fn prop_parse(ages in prop::collection::vec(0..7u32, 0..50)) {
+            let text = ages.iter().map(|a| a.to_string()).collect::<Vec<String>>().join(",");
+            prop_assert_eq!(parse_fish_ages(&text).unwrap(), ages);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day06>();
+    }
+}
\ No newline at end of file
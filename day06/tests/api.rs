@@ -0,0 +1,9 @@
+use day06::*;
+
+#[test]
+fn test_public_api() {
+    let fish_counts = make_fish_counts(&parse_fish_ages("3,4,3,1,2\n").unwrap());
+    assert_eq!(fish_counts, [0, 1, 1, 2, 1, 0, 0, 0, 0]);
+    assert_eq!(simulate(fish_counts, 3), [2, 1, 0, 0, 0, 1, 1, 1, 1]);
+    assert_eq!(simulate_count(fish_counts, 256), 26984457539);
+}
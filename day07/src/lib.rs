@@ -0,0 +1,148 @@
+//! Day 7: The Treachery of Whales. Lining up crabs for the least fuel.
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::{trace, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::Rng;
+
+/// Parses the comma separated crab positions.
+pub fn parse_crab_positions(input: &str) -> Result<Vec<i64>, ParseError> {
+    let input = Span::new(input);
+    // Distances between i32 positions fit in an i64, but their triangular part 2
+    // costs don't, so fuel is summed in an i128
+    let crabs = input.list::<i32>(',')?.into_iter().map(i64::from).collect::<Vec<i64>>();
+    if crabs.is_empty() {
+        return Err(input.at_end().error("Expected at least one crab position"));
+    }
+    Ok(crabs)
+}
+
+/// Position with the least fuel, and that fuel, when every step costs 1.
+pub fn find_min_fuel_p1(mut crabs: Vec<i64>) -> (i64, i128) {
+    // The minimum position must be on the middle crab (median)
+    assert!(!crabs.is_empty());
+    crabs.sort();
+    let min_pos = crabs[crabs.len() / 2];
+    trace!("Median of {} crabs is {}", crabs.len(), min_pos);
+    (min_pos, crabs.iter().map(|pos| (pos - min_pos).abs() as i128).sum())
+}
+
+/// Fuel to move every crab to `pos` when each step costs 1 more than the last.
+pub fn p2_fuel(crabs: &[i64], pos: i64) -> i128 {
+    crabs.iter()
+        .map(|p| (p - pos).abs() as i128)
+        .map(|dist| dist * (dist + 1) / 2)
+        .sum()
+}
+
+/// Position with the least fuel, and that fuel, with [`p2_fuel`] costs.
+pub fn find_min_fuel_p2(crabs: Vec<i64>) -> (i64, i128) {
+    // Center position (smallest sums of distances squared) i.e. mean
+    let min_pos: f64 = crabs.iter().map(|n| *n as f64).sum::<f64>() / (crabs.len() as f64);
+    // I'm not sure why rounding doesn't work, but sometimes you have to try both
+    trace!("Mean of {} crabs is {}", crabs.len(), min_pos);
+    let min_pos = min_pos.floor() as i64;
+    let a = p2_fuel(&crabs, min_pos);
+    let b = p2_fuel(&crabs, min_pos + 1);
+    trace!("Fuel to {} is {}, to {} is {}", min_pos, a, min_pos + 1, b);
+    if a < b {
+        (min_pos, a)
+    } else {
+        (min_pos + 1, b)
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    type Input = Vec<i64>;
+    type Answer1 = i128;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_crab_positions(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        find_min_fuel_p1(input.clone()).1
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        find_min_fuel_p2(input.clone()).1
+    }
+}
+
+// Crabs cluster towards the low positions, like in the real input
+impl Generator for Day07 {
+    const MIN_SIZE: usize = 1;
+
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        let crabs = (0..size)
+            .map(|_| {
+                let max = rng.random_range(1..=2000);
+                rng.random_range(0..max).to_string()
+            })
+            .collect::<Vec<String>>();
+        crabs.join(",") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_sample() {
+        let sample = "16,1,2,0,4,2,7,1,2,14";
+        let crabs = parse_crab_positions(sample).unwrap();
+        assert_eq!(find_min_fuel_p1(crabs.clone()), (2, 37));
+        assert_eq!(find_min_fuel_p2(crabs), (5, 168));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_crab_positions("16,1,-,0").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 6, "-"));
+        assert_eq!(parse_crab_positions(" \n").unwrap_err().message, "Expected at least one crab position");
+    }
+
+    // Found by fuzzing
+    #[test]
+    fn test_regressions() {
+        // Part 2 fuel overflowed an i32, then an i64
+        let crabs = parse_crab_positions("65535,0").unwrap();
+        assert_eq!(find_min_fuel_p2(crabs).1, 2 * (32767 * 32768 / 2) + 32768);
+        let crabs = parse_crab_positions("2147483647,-2147483648,2147483647,-2147483648").unwrap();
+        let dist = 1i128 << 31;
+        assert_eq!(find_min_fuel_p1(crabs.clone()), (i32::MAX as i64, 2 * (2 * dist - 1)));
+        assert_eq!(find_min_fuel_p2(crabs), (0, 2 * (dist - 1) * dist / 2 + 2 * dist * (dist + 1) / 2));
+    }
+
+    fn crabs() -> impl Strategy<Value = Vec<i64>> {
+        prop::collection::vec(0..1000i64, 1..50)
+    }
+
+    proptest! {
+        #[test]
+        fn prop_min_fuel_p1(crabs in crabs(), pos in 0..1000i64) {
+            let fuel = crabs.iter().map(|c| (c - pos).abs() as i128).sum::<i128>();
+            prop_assert!(find_min_fuel_p1(crabs).1 <= fuel);
+        }
+
+        #[test]
+        fn prop_min_fuel_p2(crabs in crabs(), pos in 0..1000i64) {
+            let (min_pos, min_fuel) = find_min_fuel_p2(crabs.clone());
+            prop_assert_eq!(p2_fuel(&crabs, min_pos), min_fuel);
+            prop_assert!(min_fuel <= p2_fuel(&crabs, pos));
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day07>();
+    }
+}
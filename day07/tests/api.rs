@@ -0,0 +1,9 @@
+use day07::*;
+
+#[test]
+fn test_public_api() {
+    let crabs = parse_crab_positions("16,1,2,0,4,2,7,1,2,14\n").unwrap();
+    assert_eq!(find_min_fuel_p1(crabs.clone()), (2, 37));
+    assert_eq!(find_min_fuel_p2(crabs.clone()), (5, 168));
+    assert_eq!(p2_fuel(&crabs, 2), 206);
+}
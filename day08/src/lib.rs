@@ -0,0 +1,272 @@
+//! Day 8: Seven Segment Search. Decoding displays with scrambled wiring.
+
+use aoc::gen::Generator;
+use aoc::parse::Span;
+use aoc::{trace, ParseError, Solution};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// A signal pattern, stored as a bitmask of its segments, a = bit 0 .. g = bit 6.
+pub type Pattern = u8;
+
+/// One display's notes: the patterns of all ten digits and the four digits shown.
+#[derive(Debug)]
+pub struct Note {
+    pub patterns: Vec<Pattern>,
+    pub outputs: Vec<Pattern>,
+}
+
+/// Parses a pattern such as `cdfbe`.
+pub fn parse_pattern(s: Span) -> Result<Pattern, ParseError> {
+    s.chars()
+        .map(|(c, span)| match c {
+            'a'..='g' => Ok(1 << (c as u8 - b'a')),
+            _ => Err(span.error(
+                format!("Unexpected segment '{}' (expected a-g)", c))),
+        })
+        .try_fold(0, |acc, x| Ok(acc | x?))
+}
+
+// Takes the next `count` patterns from `words`, which must not include the '|' separator
+fn parse_patterns<'a>(input: Span<'a>, words: &mut impl Iterator<Item = Span<'a>>, count: usize) -> Result<Vec<(Pattern, Span<'a>)>, ParseError> {
+    (0..count)
+        .map(|_| match words.next() {
+            Some(word) if word.as_str() != "|" => Ok((parse_pattern(word)?, word)),
+            _ => Err(input.at_end().error(format!("Expected {} signal patterns", count))),
+        })
+        .collect()
+}
+
+/// Parses the notes, checking that every output can be decoded.
+// Notes are parsed as a stream of words rather than lines, because the puzzle
+// sample wraps each note onto two lines after the '|'
+pub fn parse_notes(input: &str) -> Result<Vec<Note>, ParseError> {
+    let input = Span::new(input);
+    let mut words = input.words().peekable();
+    let mut notes = Vec::new();
+    while let Some(&start) = words.peek() {
+        let patterns = parse_patterns(input, &mut words, 10)?
+            .into_iter().map(|(p, _)| p).collect::<Vec<Pattern>>();
+        match words.next() {
+            Some(word) if word.as_str() == "|" => (),
+            Some(word) => return Err(word.error("Expected '|' after 10 signal patterns")),
+            None => return Err(input.at_end().error("Expected '|' after 10 signal patterns")),
+        }
+        let outputs = parse_patterns(input, &mut words, 4)?;
+
+        // Decoding relies on the patterns for 1 and 4, which are the only ones with 2 and 4 segments
+        for (digit, segments) in [(1, 2), (4, 4)] {
+            if !patterns.iter().any(|p| segment_count(*p) == segments) {
+                return Err(start.error(format!("Note has no pattern for digit {}", digit)));
+            }
+        }
+        let (one, four) = key_patterns(&patterns).unwrap();
+        if let Some((_, word)) = outputs.iter().find(|(p, _)| decode_digit(*p, one, four).is_none()) {
+            return Err(word.error(format!("Pattern '{}' doesn't match any digit", word.as_str())));
+        }
+        let outputs = outputs.into_iter().map(|(p, _)| p).collect();
+        notes.push(Note { patterns, outputs });
+    }
+    Ok(notes)
+}
+
+pub fn segment_count(pattern: Pattern) -> u32 {
+    pattern.count_ones()
+}
+
+/// Number of outputs which are 1, 4, 7 or 8, the only digits using 2, 4, 3
+/// and 7 segments respectively.
+pub fn count_unique_outputs(notes: &[Note]) -> usize {
+    notes.iter()
+        .map(|note| {
+            let unique = note.outputs.iter().filter(|p| matches!(segment_count(**p), 2 | 3 | 4 | 7)).count();
+            trace!("{}: {} of the outputs are 1, 4, 7 or 8", format_patterns(&note.outputs), unique);
+            unique
+        })
+        .sum()
+}
+
+fn format_patterns(patterns: &[Pattern]) -> String {
+    patterns.iter()
+        .map(|p| (0..7).filter(|s| p >> s & 1 == 1).map(|s| (b'a' + s) as char).collect::<String>())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// The digit `pattern` shows, given the patterns for 1 and 4.
+// Every digit is uniquely identified by its segment count combined with how
+// many segments it shares with the (always identifiable) 1 and 4
+pub fn decode_digit(pattern: Pattern, one: Pattern, four: Pattern) -> Option<usize> {
+    let with_one = segment_count(pattern & one);
+    let with_four = segment_count(pattern & four);
+    match (segment_count(pattern), with_one, with_four) {
+        (2, _, _) => Some(1),
+        (3, _, _) => Some(7),
+        (4, _, _) => Some(4),
+        (7, _, _) => Some(8),
+        (5, 2, _) => Some(3),
+        (5, _, 3) => Some(5),
+        (5, _, _) => Some(2),
+        (6, _, 4) => Some(9),
+        (6, 2, _) => Some(0),
+        (6, _, _) => Some(6),
+        _ => None,
+    }
+}
+
+/// The patterns for 1 and 4.
+pub fn key_patterns(patterns: &[Pattern]) -> Option<(Pattern, Pattern)> {
+    let find = |segments| patterns.iter().find(|p| segment_count(**p) == segments).cloned();
+    Some((find(2)?, find(4)?))
+}
+
+/// The four digit number a note's display shows.
+// Parsing checks that every output decodes
+pub fn decode_output(note: &Note) -> usize {
+    let (one, four) = key_patterns(&note.patterns).unwrap();
+    let output = note.outputs.iter()
+        .fold(0, |acc, p| acc * 10 + decode_digit(*p, one, four).unwrap());
+    trace!("{}: 1 is {}, 4 is {}, so the output is {:04}", format_patterns(&note.outputs),
+        format_patterns(&[one]), format_patterns(&[four]), output);
+    output
+}
+
+pub fn sum_outputs(notes: &[Note]) -> usize {
+    notes.iter().map(decode_output).sum()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+
+    type Input = Vec<Note>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_notes(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        count_unique_outputs(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        sum_outputs(input)
+    }
+}
+
+// Segments lit for each digit 0-9 on an unscrambled display
+const DIGIT_SEGMENTS: [&str; 10] = ["abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg"];
+
+// Each note gets its own wiring, and every pattern lists its segments in random order
+impl Generator for Day08 {
+    fn generate(rng: &mut StdRng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let mut wiring = *b"abcdefg";
+                wiring.shuffle(rng);
+                let scramble = |digit: usize, rng: &mut StdRng| {
+                    let mut segments = DIGIT_SEGMENTS[digit].bytes()
+                        .map(|s| wiring[(s - b'a') as usize] as char)
+                        .collect::<Vec<char>>();
+                    segments.shuffle(rng);
+                    segments.into_iter().collect::<String>()
+                };
+                let mut digits = (0..10).collect::<Vec<usize>>();
+                digits.shuffle(rng);
+                let patterns = digits.iter().map(|d| scramble(*d, rng)).collect::<Vec<String>>();
+                let outputs = (0..4).map(|_| scramble(rng.random_range(0..10), rng)).collect::<Vec<String>>();
+                format!("{} | {}\n", patterns.join(" "), outputs.join(" "))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn tests() {
+        let sample = "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb |
+            fdgacbe cefdb cefbgd gcbe
+            edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec |
+            fcgedb cgb dgebacf gc
+            fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef |
+            cg cg fdcagb cbg
+            fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega |
+            efabcd cedba gadfec cb
+            aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga |
+            gecf egdcabf bgf bfgea
+            fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf |
+            gebdcfa ecba ca fadegcb
+            dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf |
+            cefg dcbef fcge gbcadfe
+            bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd |
+            ed bcgafe cdgba cbgef
+            egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg |
+            gbdfcae bgc cg cgb
+            gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc |
+            fgae cfgab fg bagce";
+        let notes = parse_notes(sample).unwrap();
+        assert_eq!(notes.len(), 10);
+        assert_eq!(count_unique_outputs(&notes), 26);
+        assert_eq!(decode_output(&notes[0]), 8394);
+        assert_eq!(sum_outputs(&notes), 61229);
+    }
+
+    #[test]
+    fn test_single_line() {
+        let sample = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+        assert_eq!(decode_output(&parse_notes(sample).unwrap()[0]), 5353);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_notes("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab |\ncdfeb fcxdb cdfeb cdbaf").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 9, "x"));
+        let err = parse_notes("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab cdfeb fcadb").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 60, "cdfeb"));
+        let err = parse_notes("acedgfb cdfbe gcdfa fbcad dab | cdfeb fcadb cdfeb cdbaf").unwrap_err();
+        assert_eq!(err.message, "Expected 10 signal patterns");
+    }
+
+    // Found by fuzzing
+    #[test]
+    fn test_regressions() {
+        // Outputs that aren't any digit failed to decode
+        let err = parse_notes("ab abcd ab ab ab ab ab ab ab ab | ab a ab ab").unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (38, "Pattern 'a' doesn't match any digit"));
+    }
+
+    proptest! {
+        // Any rewiring of the segments still decodes to the digits that were displayed
+        #[test]
+        fn prop_decode(
+            wiring in Just(b"abcdefg".to_vec()).prop_shuffle(),
+            order in Just((0..10).collect::<Vec<usize>>()).prop_shuffle(),
+            outputs in prop::collection::vec(0..10usize, 4),
+        ) {
+            let scramble = |digit: usize| DIGIT_SEGMENTS[digit].bytes()
+                .map(|s| wiring[(s - b'a') as usize] as char)
+                .collect::<String>();
+            let patterns = order.iter().map(|d| scramble(*d)).collect::<Vec<String>>();
+            let shown = outputs.iter().map(|d| scramble(*d)).collect::<Vec<String>>();
+            let notes = parse_notes(&format!("{} | {}", patterns.join(" "), shown.join(" "))).unwrap();
+
+            let expected = outputs.iter().fold(0, |acc, d| acc * 10 + d);
+            prop_assert_eq!(decode_output(&notes[0]), expected);
+            let unique = outputs.iter().filter(|d| matches!(d, 1 | 4 | 7 | 8)).count();
+            prop_assert_eq!(count_unique_outputs(&notes), unique);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        aoc::gen::assert_generates::<Day08>();
+    }
+}
@@ -0,0 +1,14 @@
+use aoc::parse::Span;
+use day08::*;
+
+#[test]
+fn test_public_api() {
+    let notes = parse_notes("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf\n").unwrap();
+    let (one, four) = key_patterns(&notes[0].patterns).unwrap();
+    assert_eq!((one, four), (parse_pattern(Span::new("ab")).unwrap(), parse_pattern(Span::new("eafb")).unwrap()));
+    assert_eq!(segment_count(four), 4);
+    assert_eq!(decode_digit(parse_pattern(Span::new("dab")).unwrap(), one, four), Some(7));
+    assert_eq!(decode_output(&notes[0]), 5353);
+    assert_eq!(count_unique_outputs(&notes), 0);
+    assert_eq!(sum_outputs(&notes), 5353);
+}
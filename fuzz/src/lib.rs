@@ -0,0 +1,13 @@
+//! Fuzz targets for the puzzle parsers and solvers, run with e.g.
+//! `cargo +nightly fuzz run day04` from this directory.
+
+use aoc::Solution;
+
+/// Parsing must never panic, and any input the parser accepts must be solved,
+/// or fail with an error for parts which return a `Result`, without panicking.
+pub fn check<S: Solution>(input: &str) {
+    if let Ok(parsed) = S::parse(input) {
+        S::part1(&parsed);
+        S::part2(&parsed);
+    }
+}